@@ -1,18 +1,24 @@
-use chrono::{Datelike, Duration};
+use std::collections::HashSet;
+
+use chrono::{Datelike, Duration, TimeZone, Weekday};
 use eframe::{
-    egui::{self, Color32},
+    egui::{self, Align2, Color32},
     epi,
 };
 use egui_datepicker::*;
 
 struct ExampleApp {
     date: Date<Utc>,
+    allow_selection: bool,
+    weekdays: HashSet<Weekday>,
 }
 
 impl Default for ExampleApp {
     fn default() -> Self {
         Self {
             date: Utc::now().date(),
+            allow_selection: true,
+            weekdays: HashSet::new(),
         }
     }
 }
@@ -49,6 +55,205 @@ impl epi::App for ExampleApp {
                         .highlight_weekend_color(Color32::from_rgb(0, 196, 0)),
                 );
                 ui.end_row();
+                ui.label("Different saturday and sunday colors");
+                ui.add(
+                    DatePicker::new("saturdaysundaycolors", &mut self.date).weekend_colors(
+                        Color32::from_rgb(0, 96, 196),
+                        Color32::from_rgb(196, 0, 0),
+                    ),
+                );
+                ui.end_row();
+                ui.label("ISO week date format");
+                ui.add(DatePicker::new("isoweekformat", &mut self.date).iso_week_format());
+                ui.end_row();
+                ui.label("Ordinal date format");
+                ui.add(DatePicker::new("ordinalformat", &mut self.date).ordinal_format());
+                ui.end_row();
+                ui.label("Locked to month of the new year");
+                ui.add(
+                    DatePicker::new("lockedmonth", &mut self.date)
+                        .locked_month(Utc::today().year() + 1, 1),
+                );
+                ui.end_row();
+                ui.label("Date range from first day of month to month from today");
+                ui.add(DatePicker::new("daterange", &mut self.date).date_range(
+                    Utc::today().with_day(1).unwrap()..=Utc::today() + Duration::days(31),
+                ));
+                ui.end_row();
+                ui.label("Selected day as pill");
+                ui.add(
+                    DatePicker::new("selectedshape", &mut self.date)
+                        .selected_shape(SelectedShape::Pill, 0.0),
+                );
+                ui.end_row();
+                ui.label("Wheel of day, month and year");
+                DatePicker::new("wheel", &mut self.date).show_wheel(ui);
+                ui.end_row();
+                ui.label("Not closed by click outside");
+                ui.add(
+                    DatePicker::new("closeonoutsideclick", &mut self.date)
+                        .close_on_outside_click(false),
+                );
+                ui.end_row();
+                ui.label("Colors from visuals");
+                ui.add(
+                    DatePicker::new("colorsfromvisuals", &mut self.date)
+                        .weekend_color_from_visuals(|visuals| visuals.strong_text_color())
+                        .highlight_from_today_with_visuals(|visuals| visuals.faint_bg_color)
+                        .selected_shape(SelectedShape::Ring, 2.0)
+                        .selected_color_from_visuals(|visuals| visuals.hyperlink_color),
+                );
+                ui.end_row();
+                ui.checkbox(&mut self.allow_selection, "Selection allowed");
+                let allow_selection = self.allow_selection;
+                ui.add(
+                    DatePicker::new("onselect", &mut self.date).on_select(move |_| allow_selection),
+                );
+                ui.end_row();
+                ui.label("Year overview in 3 columns");
+                ui.add(
+                    DatePicker::new("monthgrid", &mut self.date)
+                        .months_shown(12)
+                        .month_grid(3)
+                        .step_by_page(true),
+                );
+                ui.end_row();
+                ui.label("Popup in window");
+                ui.add(
+                    DatePicker::new("popupwindow", &mut self.date)
+                        .popup_backend(PopupBackend::Window),
+                );
+                ui.end_row();
+                ui.label("Holidays with names on hover");
+                let year = Utc::today().year();
+                ui.add(DatePicker::new("holidays", &mut self.date).holidays(
+                    vec![
+                        (Utc.ymd(year, 1, 1), "New Year's Day".to_string()),
+                        (Utc.ymd(year, 12, 25), "Christmas Day".to_string()),
+                    ],
+                    Color32::from_rgb(0, 128, 0),
+                ));
+                ui.end_row();
+                ui.label("Highlight column by clicking week day");
+                ui.add(
+                    DatePicker::new("weekdayheaderclick", &mut self.date)
+                        .weekday_header_click(WeekdayHeaderAction::HighlightColumn),
+                );
+                ui.end_row();
+                ui.label("Today instead of date");
+                ui.add(
+                    DatePicker::new("todaysummarylabel", &mut self.date)
+                        .today_summary_label(Some("Today".to_string())),
+                );
+                ui.end_row();
+                ui.label("Long date format preset");
+                ui.add(
+                    DatePicker::new("formatpreset", &mut self.date)
+                        .format_preset(FormatPreset::LongDate),
+                );
+                ui.end_row();
+                ui.label("Wide popup margin");
+                ui.add(
+                    DatePicker::new("popupmargin", &mut self.date)
+                        .popup_margin(Some(egui::style::Margin::same(16.0))),
+                );
+                ui.end_row();
+                ui.label("Accelerated year dragging");
+                ui.add(
+                    DatePicker::new("yeardragacceleration", &mut self.date)
+                        .year_drag_acceleration(true),
+                );
+                ui.end_row();
+                ui.label("Crosshair on hovered day");
+                ui.add(DatePicker::new("crosshair", &mut self.date).crosshair_on_hover(true));
+                ui.end_row();
+                ui.label("Without year control");
+                ui.add(DatePicker::new("noyearcontrol", &mut self.date).year_control(false));
+                ui.end_row();
+                ui.label("Disabled days in gray");
+                ui.add(
+                    DatePicker::new("disabledcolor", &mut self.date)
+                        .min_date(Utc::today() - Duration::days(10))
+                        .max_date(Utc::today() + Duration::days(10))
+                        .disabled_color(Color32::GRAY),
+                );
+                ui.end_row();
+                ui.label("Compact header");
+                ui.add(DatePicker::new("compactheader", &mut self.date).compact_header(true));
+                ui.end_row();
+                if ui.button("Focus date picker").clicked() {
+                    ui.memory()
+                        .request_focus(egui::Id::new("focusiddatepicker"));
+                }
+                ui.add(
+                    DatePicker::new("focusid", &mut self.date)
+                        .focus_id(Some(egui::Id::new("focusiddatepicker"))),
+                );
+                ui.end_row();
+                ui.label("Grid background");
+                ui.add(
+                    DatePicker::new("gridbackground", &mut self.date)
+                        .grid_background(Color32::from_gray(64)),
+                );
+                ui.end_row();
+                ui.label("Closed by today button");
+                ui.add(DatePicker::new("closeontoday", &mut self.date).close_on_today(true));
+                ui.end_row();
+                ui.label("Week number in header");
+                ui.add(
+                    DatePicker::new("headerweeknumber", &mut self.date)
+                        .show_header_week_number(true),
+                );
+                ui.end_row();
+                ui.label("Gap between header and grid");
+                ui.add(DatePicker::new("headergridgap", &mut self.date).header_grid_gap(8.0));
+                ui.end_row();
+                ui.label("Centered grid");
+                ui.add(
+                    DatePicker::new("gridalign", &mut self.date).grid_align(egui::Align::Center),
+                );
+                ui.end_row();
+                ui.label("Selection disabled");
+                ui.add(DatePicker::new("selectenabled", &mut self.date).select_enabled(false));
+                ui.end_row();
+                ui.label("Event count badges");
+                ui.add(
+                    DatePicker::new("eventcount", &mut self.date)
+                        .event_count(|date| (date.day() % 12) as usize, Color32::RED),
+                );
+                ui.end_row();
+                ui.label("Button only opens popup");
+                ui.add(DatePicker::new("buttontoggles", &mut self.date).button_toggles(false));
+                ui.end_row();
+                ui.label("Weekday picker");
+                ui.add(WeekdayPicker::new(&mut self.weekdays));
+                ui.end_row();
+                ui.label("Month overflow rolls over");
+                ui.add(
+                    DatePicker::new("overflowpolicy", &mut self.date)
+                        .overflow_policy(OverflowPolicy::Rollover),
+                );
+                ui.end_row();
+                ui.label("Popup as wide as button");
+                ui.add_sized(
+                    [300.0, 20.0],
+                    DatePicker::new("matchtriggerwidth", &mut self.date).match_trigger_width(true),
+                );
+                ui.end_row();
+                ui.label("Salted internal ids");
+                ui.add(DatePicker::new("idsalt", &mut self.date).id_salt("second"));
+                ui.end_row();
+                ui.label("Square day cells");
+                ui.add(DatePicker::new("squarecells", &mut self.date).square_cells(true));
+                ui.end_row();
+                ui.label("Weekend color for light and dark theme");
+                ui.add(
+                    DatePicker::new("themedweekendcolor", &mut self.date).weekend_color_themed(
+                        Color32::from_rgb(196, 0, 0),
+                        Color32::from_rgb(255, 128, 128),
+                    ),
+                );
+                ui.end_row();
                 ui.label("Different weekend days, i.e. holidays, Christmas, etc");
                 ui.add(
                     DatePicker::new("differentweekenddays", &mut self.date)
@@ -67,6 +272,65 @@ impl epi::App for ExampleApp {
                         .max_date(Utc::today() + Duration::days(10)),
                 );
                 ui.end_row();
+                ui.label("Always six rows");
+                ui.add(DatePicker::new("sixrows", &mut self.date).row_policy(RowPolicy::SixRows));
+                ui.end_row();
+                ui.label("Unified header");
+                ui.add(
+                    DatePicker::new("unifiedheader", &mut self.date)
+                        .header_style(HeaderStyle::Unified),
+                );
+                ui.end_row();
+                ui.label("Popup aligned to right edge");
+                ui.add(
+                    DatePicker::new("popupanchor", &mut self.date)
+                        .popup_anchor(Align2::RIGHT_BOTTOM),
+                );
+                ui.end_row();
+                ui.label("Day numbers in top right corner");
+                ui.add(DatePicker::new("dayalign", &mut self.date).day_align(Align2::RIGHT_TOP));
+                ui.end_row();
+                ui.label("Dots in empty cells");
+                ui.add(
+                    DatePicker::new("emptycells", &mut self.date).empty_cell_ui(|ui| {
+                        ui.weak("·");
+                    }),
+                );
+                ui.end_row();
+                ui.label("Three months");
+                ui.add(DatePicker::new("threemonths", &mut self.date).months_shown(3));
+                ui.end_row();
+                ui.label("Three months with shared week days");
+                ui.add(
+                    DatePicker::new("sharedweekdays", &mut self.date)
+                        .months_shown(3)
+                        .shared_weekday_header(true),
+                );
+                ui.end_row();
+                ui.label("Selected date readout");
+                ui.add(
+                    DatePicker::new("selectionreadout", &mut self.date)
+                        .show_selection_readout(true),
+                );
+                ui.end_row();
+                ui.label("Highlight days from today");
+                ui.add(
+                    DatePicker::new("highlightfromtoday", &mut self.date)
+                        .highlight_from_today(Color32::from_rgb(0, 64, 128)),
+                );
+                ui.end_row();
+                ui.label("Shaded weekend columns");
+                ui.add(
+                    DatePicker::new("shadedweekend", &mut self.date)
+                        .shade_weekend_columns(Color32::from_rgba_unmultiplied(196, 0, 0, 24)),
+                );
+                ui.end_row();
+                ui.label("Only Fridays are allowed");
+                ui.add(
+                    DatePicker::new("allowedfridays", &mut self.date)
+                        .allowed(|date| date.weekday() == Weekday::Fri),
+                );
+                ui.end_row();
             });
         });
     }
@@ -28,7 +28,12 @@
 //!
 //! [ex]: ./examples/simple.rs
 
-use std::{fmt::Display, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    ops::RangeInclusive,
+};
 
 pub use chrono::{
     offset::{FixedOffset, Local, Utc},
@@ -37,15 +42,201 @@ pub use chrono::{
 use chrono::{prelude::*, Duration};
 use eframe::{
     egui,
-    egui::{Area, Color32, DragValue, Frame, Id, Key, Order, Response, RichText, Ui, Widget},
+    egui::{
+        style::Margin, Align, Align2, Area, Color32, Direction, DragValue, Frame, Id, Key, Layout,
+        Order, Pos2, Rect, Response, RichText, Sense, Stroke, Ui, Vec2, Visuals, Widget,
+    },
 };
 use num_traits::FromPrimitive;
 
+/// Number of week rows drawn in calendar grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowPolicy {
+    /// Only as many rows as needed for current month, i.e. four to six.
+    Dynamic,
+    /// Always six rows, so popup size doesn't change between months.
+    SixRows,
+}
+
+/// Layout of popup header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderStyle {
+    /// Separate month and year controls, each with own arrows.
+    Split,
+    /// Single "March 2024" title between arrows which step months.
+    Unified,
+}
+
+/// What navigation buttons do when step would cross minimum or maximum date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryBehavior {
+    /// Button is disabled, so limit is visible.
+    Disable,
+    /// Button stays enabled, but clicking it does nothing.
+    NoOp,
+}
+
+/// What happens with day of month when stepping month or year lands on month which doesn't
+/// have that day, e.g. January 31st plus one month gives February 28th with `Clamp` and March
+/// 3rd with `Rollover`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Day is clamped to last day of month.
+    Clamp,
+    /// Extra days are carried over into next month. If that would land back in the month stepped
+    /// from, e.g. March 31st minus one month, day is clamped instead, so step always changes
+    /// month.
+    Rollover,
+}
+
+/// Day of calendar grid computed by [`DatePicker::layout`].
+#[derive(Clone, Debug)]
+pub struct DayCell<Tz>
+where
+    Tz: TimeZone,
+{
+    /// Date of cell.
+    pub date: Date<Tz>,
+    /// True if day belongs to shown month, other cells are drawn empty.
+    pub in_month: bool,
+    /// True if day is weekend day according to `weekend_days`.
+    pub is_weekend: bool,
+    /// True if day is outside of minimum and maximum date or not allowed by `allowed`.
+    pub is_disabled: bool,
+    /// True if day is selected date according to `selection_eq`.
+    pub is_selected: bool,
+}
+
+/// Shape drawn behind selected day in selection color of current visuals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectedShape {
+    /// Filled cell.
+    Fill,
+    /// Outline of cell, day keeps its text color.
+    Ring,
+    /// Filled cell with fully rounded ends.
+    Pill,
+}
+
+/// What clicking name of week day above calendar grid does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeekdayHeaderAction {
+    /// Names are plain labels.
+    None,
+    /// Toggle highlight of whole column of that week day.
+    HighlightColumn,
+}
+
+/// Predefined date format of summary button. There is no locale support, so presets use ISO
+/// order of numbers and English names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormatPreset {
+    /// `2024-03-11`
+    ShortDate,
+    /// `Monday, 11 March 2024`
+    LongDate,
+    /// `March 2024`
+    MonthYear,
+}
+
+impl FormatPreset {
+    /// Get [chrono::format::strftime] pattern of preset.
+    pub fn pattern(self) -> &'static str {
+        match self {
+            FormatPreset::ShortDate => "%Y-%m-%d",
+            FormatPreset::LongDate => "%A, %-d %B %Y",
+            FormatPreset::MonthYear => "%B %Y",
+        }
+    }
+}
+
+/// Container in which popup is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopupBackend {
+    /// Bare foreground area.
+    Area,
+    /// Window with title bar and close button, which can be collapsed and resized.
+    Window,
+}
+
+/// Response of [`DatePicker::show`].
+pub struct DatePickerResponse {
+    /// Response of summary button, same as returned by `ui.add`.
+    pub response: Response,
+    /// True if pointer is over open popup.
+    pub popup_hovered: bool,
+    /// Rect of popup, `None` when popup is closed.
+    pub popup_rect: Option<Rect>,
+    /// Response of popup area or window, e.g. to detect dragging of movable popup. `None` when
+    /// popup is closed.
+    pub popup_response: Option<Response>,
+}
+
+/// Function drawing custom content of calendar cell.
+type CellUi<'a> = Box<dyn Fn(&mut Ui) + 'a>;
+
+/// Function receiving day and rect of its cell.
+type DayRendered<'a, Tz> = Box<dyn FnMut(&Date<Tz>, Rect) + 'a>;
+
+/// Function deciding if clicked day is selected.
+type OnSelect<'a, Tz> = Box<dyn FnMut(&Date<Tz>) -> bool + 'a>;
+
 /// Default values of fields are:
 /// - sunday_first: `false`
 /// - movable: `false`
-/// - format_string: `"%Y-%m-%d"`
+/// - format_string: `None`, pattern of `format_preset`
+/// - format_preset: `FormatPreset::ShortDate`, i.e. `"%Y-%m-%d"`
 /// - weekend_func: `date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun`
+/// - allowed_func: every date is allowed
+/// - row_policy: `RowPolicy::Dynamic`
+/// - header_style: `HeaderStyle::Split`
+/// - months_shown: `1`
+/// - popup_anchor: `Align2::LEFT_BOTTOM`
+/// - shared_weekday_header: `false`
+/// - show_selection_readout: `false`
+/// - weekend_column_color: `None`
+/// - selection_eq: `a == b`
+/// - day_align: `Align2::CENTER_CENTER`
+/// - boundary_behavior: `BoundaryBehavior::Disable`
+/// - empty_cell_ui: `None`
+/// - weekend_colors: `None`
+/// - themed_weekend_color: `None`
+/// - month_label_width: `None`, width of the longest month name
+/// - square_cells: `false`
+/// - on_day_rendered: `None`
+/// - locked_month: `None`
+/// - selected_shape: `None`, selected day is drawn as disabled button
+/// - close_on_outside_click: `true`
+/// - weekend_visuals_color: `None`
+/// - today_range_visuals_color: `None`
+/// - selected_visuals_color: `None`, selection colors of visuals
+/// - on_select: `None`
+/// - month_grid_columns: `1`
+/// - step_by_page: `false`
+/// - persist: `false`
+/// - popup_backend: `PopupBackend::Area`
+/// - holidays: none
+/// - weekday_header_action: `WeekdayHeaderAction::None`
+/// - today_summary_label: `None`
+/// - popup_margin: `None`, margin of `Frame::popup`
+/// - year_drag_acceleration: `false`
+/// - crosshair_on_hover: `false`
+/// - month_control: `true`
+/// - year_control: `true`
+/// - disabled_color: `None`
+/// - compact_header: `false`
+/// - focus_id: `None`
+/// - grid_background: `None`
+/// - close_on_today: `false`
+/// - show_header_week_number: `false`
+/// - header_grid_gap: `0.0`
+/// - grid_align: `Align::Min`
+/// - select_enabled: `true`
+/// - event_count: `None`
+/// - button_toggles: `true`
+/// - overflow_policy: `OverflowPolicy::Clamp`
+/// - match_trigger_width: `false`
+/// - internal_id: same as id
 pub struct DatePicker<'a, Tz>
 where
     Tz: TimeZone,
@@ -57,10 +248,64 @@ where
     min_date: Option<Date<Tz>>,
     sunday_first: bool,
     movable: bool,
-    format_string: String,
+    format_string: Option<String>,
+    format_preset: FormatPreset,
     weekend_color: Color32,
     weekend_func: fn(&Date<Tz>) -> bool,
     highlight_weekend: bool,
+    allowed_func: fn(&Date<Tz>) -> bool,
+    today_range_color: Option<Color32>,
+    row_policy: RowPolicy,
+    header_style: HeaderStyle,
+    months_shown: usize,
+    popup_anchor: Align2,
+    shared_weekday_header: bool,
+    show_selection_readout: bool,
+    weekend_column_color: Option<Color32>,
+    selection_eq: fn(&Date<Tz>, &Date<Tz>) -> bool,
+    day_align: Align2,
+    boundary_behavior: BoundaryBehavior,
+    empty_cell_ui: Option<CellUi<'a>>,
+    weekend_colors: Option<(Color32, Color32)>,
+    themed_weekend_color: Option<(Color32, Color32)>,
+    month_label_width: Option<usize>,
+    square_cells: bool,
+    on_day_rendered: Option<DayRendered<'a, Tz>>,
+    locked_month: Option<(i32, u32)>,
+    selected_shape: Option<(SelectedShape, f32)>,
+    close_on_outside_click: bool,
+    weekend_visuals_color: Option<fn(&Visuals) -> Color32>,
+    today_range_visuals_color: Option<fn(&Visuals) -> Color32>,
+    selected_visuals_color: Option<fn(&Visuals) -> Color32>,
+    on_select: Option<OnSelect<'a, Tz>>,
+    month_grid_columns: usize,
+    step_by_page: bool,
+    persist: bool,
+    popup_backend: PopupBackend,
+    holidays: HashMap<NaiveDate, String>,
+    holiday_color: Color32,
+    weekday_header_action: WeekdayHeaderAction,
+    today_summary_label: Option<String>,
+    popup_margin: Option<Margin>,
+    year_drag_acceleration: bool,
+    crosshair_on_hover: bool,
+    month_control: bool,
+    year_control: bool,
+    disabled_color: Option<Color32>,
+    compact_header: bool,
+    focus_id: Option<Id>,
+    grid_background: Option<Color32>,
+    close_on_today: bool,
+    show_header_week_number: bool,
+    header_grid_gap: f32,
+    grid_align: Align,
+    select_enabled: bool,
+    event_count: Option<fn(&Date<Tz>) -> usize>,
+    event_count_color: Color32,
+    button_toggles: bool,
+    overflow_policy: OverflowPolicy,
+    match_trigger_width: bool,
+    internal_id: Id,
 }
 
 impl<'a, Tz> DatePicker<'a, Tz>
@@ -70,31 +315,161 @@ where
 {
     /// Create new date picker with unique id and mutable reference to date.
     pub fn new<T: Hash>(id: T, date: &'a mut Date<Tz>) -> Self {
+        let id = Id::new(id);
         Self {
-            id: Id::new(id),
+            id,
             date,
             max_date: None,
             min_date: None,
             sunday_first: false,
             movable: false,
-            format_string: String::from("%Y-%m-%d"),
+            format_string: None,
+            format_preset: FormatPreset::ShortDate,
             weekend_color: Color32::from_rgb(196, 0, 0),
             weekend_func: |date| date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun,
             highlight_weekend: true,
+            allowed_func: |_| true,
+            today_range_color: None,
+            row_policy: RowPolicy::Dynamic,
+            header_style: HeaderStyle::Split,
+            months_shown: 1,
+            popup_anchor: Align2::LEFT_BOTTOM,
+            shared_weekday_header: false,
+            show_selection_readout: false,
+            weekend_column_color: None,
+            selection_eq: |a, b| a == b,
+            day_align: Align2::CENTER_CENTER,
+            boundary_behavior: BoundaryBehavior::Disable,
+            empty_cell_ui: None,
+            weekend_colors: None,
+            themed_weekend_color: None,
+            month_label_width: None,
+            square_cells: false,
+            on_day_rendered: None,
+            locked_month: None,
+            selected_shape: None,
+            close_on_outside_click: true,
+            weekend_visuals_color: None,
+            today_range_visuals_color: None,
+            selected_visuals_color: None,
+            on_select: None,
+            month_grid_columns: 1,
+            step_by_page: false,
+            persist: false,
+            popup_backend: PopupBackend::Area,
+            holidays: HashMap::new(),
+            holiday_color: Color32::from_rgb(196, 0, 0),
+            weekday_header_action: WeekdayHeaderAction::None,
+            today_summary_label: None,
+            popup_margin: None,
+            year_drag_acceleration: false,
+            crosshair_on_hover: false,
+            month_control: true,
+            year_control: true,
+            disabled_color: None,
+            compact_header: false,
+            focus_id: None,
+            grid_background: None,
+            close_on_today: false,
+            show_header_week_number: false,
+            header_grid_gap: 0.0,
+            grid_align: Align::Min,
+            select_enabled: true,
+            event_count: None,
+            event_count_color: Color32::from_rgb(196, 0, 0),
+            button_toggles: true,
+            overflow_policy: OverflowPolicy::Clamp,
+            match_trigger_width: false,
+            internal_id: id,
         }
     }
 
-    /// Sets the minimum date that can be set.
+    /// Sets the minimum date that can be set. If it's later than maximum date, both are swapped.
     /// Default is None
     pub fn min_date(mut self, min_date: Date<Tz>) -> Self {
         self.min_date = Some(min_date);
+        self.fix_inverted_bounds();
         self
     }
 
-    /// Sets the maximum date that can be set.
+    /// Sets the maximum date that can be set. If it's earlier than minimum date, both are swapped.
     /// Default is None
     pub fn max_date(mut self, max_date: Date<Tz>) -> Self {
         self.max_date = Some(max_date);
+        self.fix_inverted_bounds();
+        self
+    }
+
+    /// Set width in characters to which month name in header is padded, so header doesn't move
+    /// when month changes. With None width of the longest month name is used.
+    /// Default is None
+    #[must_use]
+    pub fn month_label_width(mut self, width: Option<usize>) -> Self {
+        self.month_label_width = width;
+        self
+    }
+
+    /// Always show given month regardless of selected date and replace month, year and today
+    /// controls with plain title, so calendar can't be navigated. Days of the month can still be
+    /// selected. Invalid month is ignored.
+    /// Default is None
+    #[must_use]
+    pub fn locked_month(mut self, year: i32, month: u32) -> Self {
+        self.locked_month = Some((year, month));
+        self
+    }
+
+    /// Sets both minimum and maximum date from ends of range. If range is inverted, ends are
+    /// swapped.
+    /// Default is None
+    #[must_use]
+    pub fn date_range(self, range: RangeInclusive<Date<Tz>>) -> Self {
+        let (min_date, max_date) = range.into_inner();
+        self.min_date(min_date).max_date(max_date)
+    }
+
+    /// If flag is set to true then speed of year drag value grows with speed of pointer, so slow
+    /// drags step single years precisely and fast drags reach distant years quickly. Otherwise
+    /// speed is one year per point.
+    /// Default is false
+    #[must_use]
+    pub fn year_drag_acceleration(mut self, flag: bool) -> Self {
+        self.year_drag_acceleration = flag;
+        self
+    }
+
+    /// If flag is set to false then month name and its arrows are hidden from header. Applies to
+    /// `HeaderStyle::Split`.
+    /// Default is true
+    #[must_use]
+    pub fn month_control(mut self, flag: bool) -> Self {
+        self.month_control = flag;
+        self
+    }
+
+    /// If flag is set to false then year drag value and its arrows are hidden from header.
+    /// Applies to `HeaderStyle::Split`.
+    /// Default is true
+    #[must_use]
+    pub fn year_control(mut self, flag: bool) -> Self {
+        self.year_control = flag;
+        self
+    }
+
+    /// If flag is set to true then header is only "March 2024" title between arrows stepping
+    /// months, without today button, for narrow layouts. Overrides `header_style`.
+    /// Default is false
+    #[must_use]
+    pub fn compact_header(mut self, flag: bool) -> Self {
+        self.compact_header = flag;
+        self
+    }
+
+    /// Set what month and year buttons do when step would cross minimum or maximum date.
+    /// Default is `BoundaryBehavior::Disable`
+    #[must_use]
+    pub fn boundary_behavior(mut self, behavior: BoundaryBehavior) -> Self {
+        self.boundary_behavior = behavior;
         self
     }
 
@@ -114,11 +489,42 @@ where
         self
     }
 
-    ///Set date format.
+    ///Set date format. It takes precedence over `format_preset`.
     ///See the [chrono::format::strftime](https://docs.rs/chrono/0.4.19/chrono/format/strftime/index.html) for the specification.
     #[must_use]
     pub fn date_format(mut self, new_format: &impl ToString) -> Self {
-        self.format_string = new_format.to_string();
+        self.format_string = Some(new_format.to_string());
+        self
+    }
+
+    ///Set predefined date format, used unless format is set by `date_format`.
+    ///Default is `FormatPreset::ShortDate`
+    #[must_use]
+    pub fn format_preset(mut self, preset: FormatPreset) -> Self {
+        self.format_preset = preset;
+        self
+    }
+
+    ///Set date format to ISO week date, e.g. `2024-W11-1`. Shortcut for
+    ///`date_format(&"%G-W%V-%u")`, year is ISO week-numbering year, which differs from calendar
+    ///year around new year.
+    #[must_use]
+    pub fn iso_week_format(self) -> Self {
+        self.date_format(&"%G-W%V-%u")
+    }
+
+    ///Set date format to ordinal date, e.g. `2024-075`. Shortcut for `date_format(&"%Y-%j")`.
+    #[must_use]
+    pub fn ordinal_format(self) -> Self {
+        self.date_format(&"%Y-%j")
+    }
+
+    ///Set text shown on summary button instead of formatted date when today is selected, e.g.
+    ///`Some("Today".to_string())`. Formatted date is then shown on hover.
+    ///Default is None
+    #[must_use]
+    pub fn today_summary_label(mut self, label: Option<String>) -> Self {
+        self.today_summary_label = label;
         self
     }
 
@@ -137,208 +543,2356 @@ where
         self
     }
 
+    ///Set separate highlighting colors for saturdays and sundays. Other days decided as weekend
+    ///by `weekend_days` keep `highlight_weekend_color`.
+    #[must_use]
+    pub fn weekend_colors(mut self, saturday: Color32, sunday: Color32) -> Self {
+        self.weekend_colors = Some((saturday, sunday));
+        self
+    }
+
+    ///Set weekends highlighting color for light and dark visuals. Color is picked each frame from
+    ///`ui.visuals().dark_mode`, so it follows theme changes. Overrides `highlight_weekend_color`,
+    ///but not `weekend_colors`.
+    ///Default is None
+    #[must_use]
+    pub fn weekend_color_themed(mut self, light: Color32, dark: Color32) -> Self {
+        self.themed_weekend_color = Some((light, dark));
+        self
+    }
+
+    ///Set function picking weekends highlighting color from current visuals, e.g.
+    ///`|visuals| visuals.strong_text_color()`. It's resolved every frame, so color follows theme
+    ///changes. Overrides `highlight_weekend_color`, but not `weekend_colors` and
+    ///`weekend_color_themed`.
+    ///Default is None
+    #[must_use]
+    pub fn weekend_color_from_visuals(mut self, color: fn(&Visuals) -> Color32) -> Self {
+        self.weekend_visuals_color = Some(color);
+        self
+    }
+
+    /// Set holidays with their names. Holidays are drawn with given text color, which takes
+    /// precedence over weekend color, and show their name on hover. Holidays are kept in hash map
+    /// by date, so lookup cost per day doesn't grow with number of holidays.
+    /// Default is no holidays
+    #[must_use]
+    pub fn holidays(
+        mut self,
+        holidays: impl IntoIterator<Item = (Date<Tz>, String)>,
+        color: Color32,
+    ) -> Self {
+        self.holidays = holidays
+            .into_iter()
+            .map(|(date, name)| (date.naive_local(), name))
+            .collect();
+        self.holiday_color = color;
+        self
+    }
+
+    /// Set function, which returns number of events of day. Days with any events get badge with
+    /// that number in their top right corner drawn with given color, numbers above 9 are shown
+    /// as "9+".
+    /// Default is None
+    #[must_use]
+    pub fn event_count(mut self, count: fn(&Date<Tz>) -> usize, color: Color32) -> Self {
+        self.event_count = Some(count);
+        self.event_count_color = color;
+        self
+    }
+
     /// Set function, which will decide if date is a weekend day or not.
     pub fn weekend_days(mut self, is_weekend: fn(&Date<Tz>) -> bool) -> Self {
         self.weekend_func = is_weekend;
         self
     }
 
-    /// Draw names of week days as 7 columns of grid without calling `Ui::end_row`
-    fn show_grid_header(&mut self, ui: &mut Ui) {
-        let day_indexes = if self.sunday_first {
-            [6, 0, 1, 2, 3, 4, 5]
-        } else {
-            [0, 1, 2, 3, 4, 5, 6]
-        };
-        for i in day_indexes {
-            let b = Weekday::from_u8(i).unwrap();
-            ui.label(b.to_string());
-        }
+    /// Paint rounded rect of given color behind each calendar grid, e.g. to set grid apart when
+    /// it's drawn by `show_in_rect` without popup frame.
+    /// Default is None
+    #[must_use]
+    pub fn grid_background(mut self, color: Color32) -> Self {
+        self.grid_background = Some(color);
+        self
     }
 
-    /// Get number of days between first day of the month and Monday ( or Sunday if field
-    /// `sunday_first` is set to `true` )
-    fn get_start_offset_of_calendar(&self, first_day: &Date<Tz>) -> u32 {
-        if self.sunday_first {
-            first_day.weekday().num_days_from_sunday()
-        } else {
-            first_day.weekday().num_days_from_monday()
-        }
+    /// Shade background of weekend columns, including names of week days, with given color.
+    /// Weekend days are decided by function set with `weekend_days`.
+    /// Default is None
+    #[must_use]
+    pub fn shade_weekend_columns(mut self, color: Color32) -> Self {
+        self.weekend_column_color = Some(color);
+        self
     }
 
-    /// Get number of days between first day of the next month and Monday ( or Sunday if field
-    /// `sunday_first` is set to `true` )
-    fn get_end_offset_of_calendar(&self, first_day: &Date<Tz>) -> u32 {
-        if self.sunday_first {
-            (7 - (first_day).weekday().num_days_from_sunday()) % 7
-        } else {
-            (7 - (first_day).weekday().num_days_from_monday()) % 7
-        }
+    /// If flag is set to true then day cells are squares of equal size, side is width of the
+    /// widest week day name or day number, but at least height of a button.
+    /// Default is false
+    #[must_use]
+    pub fn square_cells(mut self, flag: bool) -> Self {
+        self.square_cells = flag;
+        self
     }
 
-    fn show_calendar_grid(&mut self, ui: &mut Ui) {
-        egui::Grid::new("calendar").show(ui, |ui| {
-            self.show_grid_header(ui);
-            let first_day_of_current_month = self.date.with_day(1).unwrap();
-            let start_offset = self.get_start_offset_of_calendar(&first_day_of_current_month);
-            let days_in_month = get_days_from_month(self.date.year(), self.date.month());
-            let first_day_of_next_month =
-                first_day_of_current_month.clone() + Duration::days(days_in_month);
-            let end_offset = self.get_end_offset_of_calendar(&first_day_of_next_month);
-            let start_date = first_day_of_current_month - Duration::days(start_offset.into());
-            for i in 0..(start_offset as i64 + days_in_month + end_offset as i64) {
-                if i % 7 == 0 {
-                    ui.end_row();
-                }
-                let d = start_date.clone() + Duration::days(i);
-                self.show_day_button(d, ui);
-            }
-        });
+    /// If flag is set to true then popup is as wide as summary button, or trigger passed to
+    /// `popup_for`, and day columns are widened to fill it. Columns are never narrower than
+    /// without this flag, so popup of narrow button is wider than the button.
+    /// Default is false
+    #[must_use]
+    pub fn match_trigger_width(mut self, flag: bool) -> Self {
+        self.match_trigger_width = flag;
+        self
     }
 
-    fn show_day_button(&mut self, date: Date<Tz>, ui: &mut Ui) {
-        ui.add_enabled_ui(self.date != &date, |ui| {
-            ui.centered_and_justified(|ui| {
-                if self.date.month() != date.month() {
-                    return;
-                }
-                if matches!(&self.min_date, Some(min_date) if min_date > &date)
-                    || matches!(&self.max_date, Some(max_date) if max_date < &date)
-                {
-                    ui.set_enabled(false);
-                }
-                if self.highlight_weekend && (self.weekend_func)(&date) {
-                    ui.style_mut().visuals.override_text_color = Some(self.weekend_color);
-                }
-                if ui.button(date.day().to_string()).clicked() {
-                    *self.date = date;
-                }
-            });
-        });
+    /// Same as `highlight_from_today`, but color is picked from current visuals every frame, e.g.
+    /// `|visuals| visuals.faint_bg_color`. Overrides color set by `highlight_from_today`.
+    /// Default is None
+    #[must_use]
+    pub fn highlight_from_today_with_visuals(mut self, color: fn(&Visuals) -> Color32) -> Self {
+        self.today_range_visuals_color = Some(color);
+        self
     }
 
-    /// Draw current month and buttons for next and previous month.
-    fn show_header(&mut self, ui: &mut Ui) {
-        ui.horizontal(|ui| {
-            self.show_month_control(ui);
-            self.show_year_control(ui);
-            if ui.button("Today").clicked() {
-                *self.date = Utc::now().with_timezone(&self.date.timezone()).date();
-            }
-        });
+    /// Set function picking color of `selected_shape` from current visuals, e.g.
+    /// `|visuals| visuals.hyperlink_color`. With None selection colors of visuals are used.
+    /// Default is None
+    #[must_use]
+    pub fn selected_color_from_visuals(mut self, color: fn(&Visuals) -> Color32) -> Self {
+        self.selected_visuals_color = Some(color);
+        self
     }
 
-    /// Draw button with text and add duration to current date when that button is clicked.
-    fn date_step_button(&mut self, ui: &mut Ui, text: impl ToString, duration: Duration) {
-        if ui.button(text.to_string()).clicked() {
-            let new_date = self.date.clone() + duration;
+    /// Draw selected day highlighted with given shape instead of as disabled button. Rounding
+    /// applies to `Fill` and `Ring`. Highlight takes precedence over today range fill, and for
+    /// filled shapes over weekend color.
+    /// Default is None
+    #[must_use]
+    pub fn selected_shape(mut self, shape: SelectedShape, rounding: f32) -> Self {
+        self.selected_shape = Some((shape, rounding));
+        self
+    }
 
-            if matches!(&self.min_date, Some(min_date) if min_date.year() > new_date.year() || (min_date.year() == new_date.year() && min_date.month() > new_date.month()))
-                || matches!(&self.max_date, Some(max_date) if max_date.year() < new_date.year() || (max_date.year() == new_date.year() && max_date.month() < new_date.month()))
-            {
-                return;
-            }
+    /// Set what clicking name of week day above calendar grid does.
+    /// Default is `WeekdayHeaderAction::None`
+    #[must_use]
+    pub fn weekday_header_click(mut self, action: WeekdayHeaderAction) -> Self {
+        self.weekday_header_action = action;
+        self
+    }
 
-            *self.date = new_date;
-        }
+    /// If flag is set to true then week row and week day column of hovered day are softly tinted.
+    /// Default is false
+    #[must_use]
+    pub fn crosshair_on_hover(mut self, flag: bool) -> Self {
+        self.crosshair_on_hover = flag;
+        self
     }
 
-    /// Draw drag value widget with current year and two buttons which substract and add 365 days
-    /// to current date.
-    fn show_year_control(&mut self, ui: &mut Ui) {
-        self.date_step_button(ui, "<", Duration::days(-365));
+    /// Draw days which can't be selected, because they are outside of minimum and maximum date or
+    /// not allowed by `allowed`, as plain text of given color instead of faded buttons. Color
+    /// takes precedence over weekend and holiday colors.
+    /// Default is None
+    #[must_use]
+    pub fn disabled_color(mut self, color: Color32) -> Self {
+        self.disabled_color = Some(color);
+        self
+    }
 
-        let min_drag = self
-            .min_date
-            .as_ref()
-            .map_or(f64::NEG_INFINITY, |date| date.year() as f64);
-        let max_drag = self
-            .max_date
-            .as_ref()
-            .map_or(f64::INFINITY, |date| date.year() as f64);
+    /// Set function, which will decide if date can be selected. Only dates for which it returns
+    /// true are clickable, the rest are drawn disabled. Applies together with `min_date` and
+    /// `max_date`, so a date must pass both.
+    #[must_use]
+    pub fn allowed(mut self, is_allowed: fn(&Date<Tz>) -> bool) -> Self {
+        self.allowed_func = is_allowed;
+        self
+    }
 
-        let mut drag_year = self.date.year();
-        ui.add(DragValue::new(&mut drag_year).clamp_range(min_drag..=max_drag));
+    /// Set function, which will decide if day of calendar is the selected date. Called with
+    /// selected date and day of calendar.
+    /// Default compares dates with `==`
+    #[must_use]
+    pub fn selection_eq(mut self, is_selected: fn(&Date<Tz>, &Date<Tz>) -> bool) -> Self {
+        self.selection_eq = is_selected;
+        self
+    }
 
-        if drag_year != self.date.year() {
-            *self.date = self.date.with_year(drag_year).unwrap();
-        }
-        self.date_step_button(ui, ">", Duration::days(365));
+    /// Shade days between today and selected date (inclusive) with given color. Works in both
+    /// directions, so selected date can be before today.
+    /// Default is None
+    #[must_use]
+    pub fn highlight_from_today(mut self, color: Color32) -> Self {
+        self.today_range_color = Some(color);
+        self
     }
 
-    /// Draw label(will be combobox in future) with current month and two buttons which substract and add 30 days
-    /// to current date.
-    fn show_month_control(&mut self, ui: &mut Ui) {
-        self.date_step_button(ui, "<", Duration::days(-30));
-        let month_string = chrono::Month::from_u32(self.date.month()).unwrap().name();
-        // TODO: When https://github.com/emilk/egui/pull/543 is merged try to change label to combo box.
-        ui.add(egui::Label::new(
-            RichText::new(format!("{: <9}", month_string)).text_style(egui::TextStyle::Monospace),
-        ));
-        // let mut selected = self.date.month0() as usize;
-        // egui::ComboBox::from_id_source(self.id.with("month_combo_box"))
-        //     .selected_text(selected)
-        //     .show_index(ui, &mut selected, 12, |i| {
-        //         chrono::Month::from_usize(i + 1).unwrap().name().to_string()
-        //     });
-        // if selected != self.date.month0() as usize {
-        //     *self.date = self.date.with_month0(selected as u32).unwrap();
-        // }
-        self.date_step_button(ui, ">", Duration::days(30));
+    /// Set how many week rows calendar grid has.
+    /// Default is `RowPolicy::Dynamic`
+    #[must_use]
+    pub fn row_policy(mut self, policy: RowPolicy) -> Self {
+        self.row_policy = policy;
+        self
     }
-}
 
-impl<'a, Tz> Widget for DatePicker<'a, Tz>
-where
-    Tz: TimeZone,
-    Tz::Offset: Display,
-{
-    fn ui(mut self, ui: &mut Ui) -> Response {
-        let formated_date = self.date.format(&self.format_string);
-        let button_response = ui.button(formated_date.to_string());
-        if button_response.clicked() {
-            ui.memory().toggle_popup(self.id);
-        }
+    /// Set layout of month and year controls in popup header.
+    /// Default is `HeaderStyle::Split`
+    #[must_use]
+    pub fn header_style(mut self, style: HeaderStyle) -> Self {
+        self.header_style = style;
+        self
+    }
 
-        if ui.memory().is_popup_open(self.id) {
-            let mut area = Area::new(self.id)
-                .order(Order::Foreground)
-                .default_pos(button_response.rect.left_bottom());
-            if !self.movable {
-                area = area.movable(false);
-            }
-            let area_response = area
-                .show(ui.ctx(), |ui| {
-                    Frame::popup(ui.style()).show(ui, |ui| {
-                        self.show_header(ui);
-                        self.show_calendar_grid(ui);
-                    });
-                })
-                .response;
+    /// Set number of consecutive months shown in popup. With more than one month, they are
+    /// stacked in vertical scroll area, each with own label, starting from month of selected
+    /// date. Header controls still step the selected date.
+    /// Default is 1
+    #[must_use]
+    pub fn months_shown(mut self, count: usize) -> Self {
+        self.months_shown = count;
+        self
+    }
 
-            if !button_response.clicked()
-                && (ui.input().key_pressed(Key::Escape) || area_response.clicked_elsewhere())
-            {
-                ui.memory().toggle_popup(self.id);
-            }
-        }
-        button_response
+    /// Set number of columns into which months set by `months_shown` are arranged, e.g. 3 columns
+    /// of 12 months make a year overview. Months fill rows from left to right.
+    /// Default is 1
+    #[must_use]
+    pub fn month_grid(mut self, columns: usize) -> Self {
+        self.month_grid_columns = columns.max(1);
+        self
     }
-}
 
-// https://stackoverflow.com/a/58188385
-fn get_days_from_month(year: i32, month: u32) -> i64 {
-    NaiveDate::from_ymd(
-        match month {
-            12 => year + 1,
-            _ => year,
-        },
-        match month {
-            12 => 1,
-            _ => month + 1,
-        },
-        1,
-    )
-    .signed_duration_since(NaiveDate::from_ymd(year, month, 1))
-    .num_days()
+    /// If flag is set to true then month arrows step by all months shown at once instead of by
+    /// one month, so next click shows following page of months.
+    /// Default is false
+    #[must_use]
+    pub fn step_by_page(mut self, flag: bool) -> Self {
+        self.step_by_page = flag;
+        self
+    }
+
+    /// If flag is set to true and more than one month is shown, names of week days are drawn
+    /// once above all months instead of above each month. Applies only with single column of
+    /// months.
+    /// Default is false
+    #[must_use]
+    pub fn shared_weekday_header(mut self, flag: bool) -> Self {
+        self.shared_weekday_header = flag;
+        self
+    }
+
+    /// If flag is set to true then ISO week number of selected date, e.g. "Week 11", is shown at
+    /// the end of popup header.
+    /// Default is false
+    #[must_use]
+    pub fn show_header_week_number(mut self, flag: bool) -> Self {
+        self.show_header_week_number = flag;
+        self
+    }
+
+    /// If flag is set to true then selected date is spelled out below popup header. While pointer
+    /// is over a day which can be selected, that day is shown instead, labeled as a preview.
+    /// Default is false
+    #[must_use]
+    pub fn show_selection_readout(mut self, flag: bool) -> Self {
+        self.show_selection_readout = flag;
+        self
+    }
+
+    /// Set where day number is placed within its cell. Centered day buttons fill whole cell,
+    /// other alignments keep button at its natural size, e.g. `Align2::RIGHT_TOP` leaves room
+    /// below number like many desktop calendars.
+    /// Default is `Align2::CENTER_CENTER`
+    #[must_use]
+    pub fn day_align(mut self, align: Align2) -> Self {
+        self.day_align = align;
+        self
+    }
+
+    /// Set function, which is called with clicked day before it's selected. If it returns false,
+    /// date is left unchanged and popup stays open. Unlike `allowed` it can depend on state at
+    /// time of click. It's called only for clicks on days, not for navigation.
+    /// Default is None
+    #[must_use]
+    pub fn on_select(mut self, callback: impl FnMut(&Date<Tz>) -> bool + 'a) -> Self {
+        self.on_select = Some(Box::new(callback));
+        self
+    }
+
+    /// Whether days can be clicked to select them. When false, calendar can still be navigated
+    /// with month and year controls, only days don't react to clicks.
+    /// Default is true
+    #[must_use]
+    pub fn select_enabled(mut self, flag: bool) -> Self {
+        self.select_enabled = flag;
+        self
+    }
+
+    /// Set function, which draws content of empty cells before first and after last day of
+    /// month. It's not called for days of month.
+    /// Default is None
+    #[must_use]
+    pub fn empty_cell_ui(mut self, add_contents: impl Fn(&mut Ui) + 'a) -> Self {
+        self.empty_cell_ui = Some(Box::new(add_contents));
+        self
+    }
+
+    /// Set function, which is called with every drawn day of month and rect of its button, e.g. to
+    /// paint overlays or hit-test aligned with grid. Rects are in screen coordinates of current
+    /// frame. It's not called for empty cells.
+    /// Default is None
+    #[must_use]
+    pub fn on_day_rendered(mut self, callback: impl FnMut(&Date<Tz>, Rect) + 'a) -> Self {
+        self.on_day_rendered = Some(Box::new(callback));
+        self
+    }
+
+    /// If flag is set to false then popup isn't closed by clicking outside of it, only by
+    /// summary button or Escape key.
+    /// Default is true
+    #[must_use]
+    pub fn close_on_outside_click(mut self, flag: bool) -> Self {
+        self.close_on_outside_click = flag;
+        self
+    }
+
+    /// If flag is set to false then clicking summary button, or trigger passed to `popup_for`,
+    /// only opens popup and doesn't close it when it's already open.
+    /// Default is true
+    #[must_use]
+    pub fn button_toggles(mut self, flag: bool) -> Self {
+        self.button_toggles = flag;
+        self
+    }
+
+    /// Set what happens with day of month when month and year arrows, year drag value or wheel
+    /// land on month which doesn't have that day.
+    /// Default is `OverflowPolicy::Clamp`
+    #[must_use]
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// If flag is set to true then open state of popup is stored in persisted egui memory under
+    /// id of date picker, so popup which was open when app was closed is opened again on start.
+    /// Takes effect only when app has egui persistence enabled.
+    /// Default is false
+    #[must_use]
+    pub fn persist(mut self, flag: bool) -> Self {
+        self.persist = flag;
+        self
+    }
+
+    /// Mix salt into ids of grids, day buttons and state kept in egui memory, so pickers created
+    /// with the same id don't share them. Popup is still opened and closed under id passed to
+    /// `new`, so `is_open` and triggers of `popup_for` aren't affected.
+    /// Default is no salt
+    #[must_use]
+    pub fn id_salt(mut self, salt: impl Hash) -> Self {
+        self.internal_id = self.id.with(salt);
+        self
+    }
+
+    /// Set container in which popup is drawn. `PopupBackend::Window` gives standard window
+    /// behavior and ordering, its close button closes popup.
+    /// Default is `PopupBackend::Area`
+    #[must_use]
+    pub fn popup_backend(mut self, backend: PopupBackend) -> Self {
+        self.popup_backend = backend;
+        self
+    }
+
+    /// Set inner margin of popup frame, i.e. gap between its border and content. With None
+    /// margin of style is used.
+    /// Default is None
+    #[must_use]
+    pub fn popup_margin(mut self, margin: Option<Margin>) -> Self {
+        self.popup_margin = margin;
+        self
+    }
+
+    /// Set id of summary button, so it can be focused with `memory.request_focus` and take part
+    /// in focus chain of form. With None button gets automatic id of egui.
+    /// Default is None
+    #[must_use]
+    pub fn focus_id(mut self, id: Option<Id>) -> Self {
+        self.focus_id = id;
+        self
+    }
+
+    /// If flag is set to true then clicking today button also closes popup.
+    /// Default is false
+    #[must_use]
+    pub fn close_on_today(mut self, flag: bool) -> Self {
+        self.close_on_today = flag;
+        self
+    }
+
+    /// Set additional vertical space between header and calendar grid.
+    /// Default is 0.0
+    #[must_use]
+    pub fn header_grid_gap(mut self, gap: f32) -> Self {
+        self.header_grid_gap = gap;
+        self
+    }
+
+    /// Set how calendar grid, together with its weekday header, is placed horizontally within
+    /// width of popup or rect given to `show_in_rect`.
+    /// Default is `Align::Min`
+    #[must_use]
+    pub fn grid_align(mut self, align: Align) -> Self {
+        self.grid_align = align;
+        self
+    }
+
+    /// Set where popup is placed relative to summary button. Horizontal align picks which popup
+    /// edge lines up with button, vertical `BOTTOM` places popup below button, `TOP` above it
+    /// and `CENTER` over it.
+    /// Default is `Align2::LEFT_BOTTOM`
+    #[must_use]
+    pub fn popup_anchor(mut self, anchor: Align2) -> Self {
+        self.popup_anchor = anchor;
+        self
+    }
+
+    /// Get current date in timezone of selected date.
+    fn today(&self) -> Date<Tz> {
+        Utc::now().with_timezone(&self.date.timezone()).date()
+    }
+
+    /// Get date to which Today button moves current date, i.e. current date moved into bounds.
+    fn get_today_in_bounds(&self) -> Date<Tz> {
+        self.clamp_to_bounds(self.today())
+    }
+
+    /// Draw names of week days as 7 columns of grid without calling `Ui::end_row`. `week_start`
+    /// is first date of grid and decides which columns are weekend columns.
+    fn show_grid_header(&mut self, ui: &mut Ui, week_start: &Date<Tz>) {
+        let highlighted_id = self.internal_id.with("highlighted_weekday");
+        for (column, b) in get_weekdays(self.sunday_first).iter().copied().enumerate() {
+            if let Some(date) = add_days(week_start, column as i64) {
+                self.shade_column_cell(ui, &date);
+            }
+            match self.weekday_header_action {
+                WeekdayHeaderAction::None => {
+                    ui.label(b.to_string());
+                }
+                WeekdayHeaderAction::HighlightColumn => {
+                    if ui
+                        .add(egui::Button::new(b.to_string()).frame(false))
+                        .clicked()
+                    {
+                        let highlighted = ui.memory().data.get_temp::<Weekday>(highlighted_id);
+                        if highlighted == Some(b) {
+                            ui.memory().data.remove::<Weekday>(highlighted_id);
+                        } else {
+                            ui.memory().data.insert_temp(highlighted_id, b);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Paint `weekend_column_color` over remaining space of grid cell if date is a weekend day,
+    /// and selection color if its week day column was highlighted by clicking its name. Must be
+    /// called before adding cell content, so it's drawn under it.
+    fn shade_column_cell(&self, ui: &mut Ui, date: &Date<Tz>) {
+        // Cover gaps between rows, so column looks continuous
+        let rect = ui
+            .available_rect_before_wrap()
+            .expand2(egui::vec2(0.0, ui.spacing().item_spacing.y / 2.0));
+        if let Some(color) = self.weekend_column_color {
+            if (self.weekend_func)(date) {
+                ui.painter().rect_filled(rect, 0.0, color);
+            }
+        }
+        if self.weekday_header_action == WeekdayHeaderAction::HighlightColumn {
+            let highlighted = ui
+                .memory()
+                .data
+                .get_temp::<Weekday>(self.internal_id.with("highlighted_weekday"));
+            if highlighted == Some(date.weekday()) {
+                let color = ui.visuals().selection.bg_fill.linear_multiply(0.3);
+                ui.painter().rect_filled(rect, 0.0, color);
+            }
+        }
+    }
+
+    /// Tint remaining space of grid cell if date shares week row or week day column with hovered
+    /// day. Must be called before adding cell content, so it's drawn under it.
+    fn shade_crosshair_cell(&self, ui: &mut Ui, date: &Date<Tz>) {
+        if !self.crosshair_on_hover {
+            return;
+        }
+        let crosshair_day = ui
+            .memory()
+            .data
+            .get_temp::<NaiveDate>(self.internal_id.with("crosshair_day"));
+        if let Some(day) = crosshair_day {
+            let date = date.naive_local();
+            let week_start = |day| get_week_start(day, self.sunday_first);
+            // Week start can be out of range of `NaiveDate` in its first week, then row isn't
+            // shaded
+            let same_row = matches!(
+                (week_start(day), week_start(date)),
+                (Some(day_week), Some(date_week)) if day_week == date_week
+            );
+            if date != day && (same_row || day.weekday() == date.weekday()) {
+                let rect = ui.available_rect_before_wrap();
+                let color = ui.visuals().selection.bg_fill.linear_multiply(0.15);
+                ui.painter().rect_filled(rect, 0.0, color);
+            }
+        }
+    }
+
+    /// Get number of days between first day of the month and Monday ( or Sunday if field
+    /// `sunday_first` is set to `true` )
+    fn get_start_offset_of_calendar(&self, first_day: &Date<Tz>) -> u32 {
+        if self.sunday_first {
+            first_day.weekday().num_days_from_sunday()
+        } else {
+            first_day.weekday().num_days_from_monday()
+        }
+    }
+
+    /// Get number of days between first day of the next month and Monday ( or Sunday if field
+    /// `sunday_first` is set to `true` )
+    fn get_end_offset_of_calendar(&self, first_day: &Date<Tz>) -> u32 {
+        if self.sunday_first {
+            (7 - (first_day).weekday().num_days_from_sunday()) % 7
+        } else {
+            (7 - (first_day).weekday().num_days_from_monday()) % 7
+        }
+    }
+
+    /// Get first and last date shown in calendar grid, including days of previous and next month
+    /// which fill first and last week. Bounds follow month of selected date, or `locked_month`,
+    /// and `row_policy`.
+    /// With `months_shown` greater than one, bounds are of the first shown month.
+    pub fn view_bounds(&self) -> (Date<Tz>, Date<Tz>) {
+        self.get_grid_bounds(&self.get_first_shown_day())
+    }
+
+    /// Compute calendar grid of first shown month as rows of weeks without drawing it. This is
+    /// the same computation drawing is based on, including swapped inverted bounds, so it can be
+    /// used for tests or to render calendar by other means.
+    /// ```no_run
+    /// # use egui_datepicker::{Date, DatePicker, Utc};
+    /// # fn f(date: &mut Date<Utc>) {
+    /// let weeks = DatePicker::new("layout_datepicker", date).layout();
+    /// let weekend_days = weeks.iter().flatten().filter(|cell| cell.in_month && cell.is_weekend);
+    /// # }
+    /// ```
+    pub fn layout(&self) -> Vec<Vec<DayCell<Tz>>> {
+        let first_day = self.get_first_shown_day();
+        let (start_date, end_date) = self.get_grid_bounds(&first_day);
+        let days_count = end_date
+            .signed_duration_since(start_date.clone())
+            .num_days()
+            + 1;
+        let cells: Vec<DayCell<Tz>> = (0..days_count)
+            .filter_map(|i| add_days(&start_date, i))
+            .map(|date| DayCell {
+                in_month: date.month() == first_day.month(),
+                is_weekend: (self.weekend_func)(&date),
+                is_disabled: !self.is_day_allowed(&date),
+                is_selected: (self.selection_eq)(self.date, &date),
+                date,
+            })
+            .collect();
+        cells.chunks(7).map(|week| week.to_vec()).collect()
+    }
+
+    /// Get first day of month shown first in popup.
+    fn get_first_shown_day(&self) -> Date<Tz> {
+        self.locked_month
+            .and_then(|(year, month)| self.date.timezone().ymd_opt(year, month, 1).single())
+            .unwrap_or_else(|| self.date.with_day(1).unwrap())
+    }
+
+    /// Get first and last date of calendar grid for month starting with `first_day`.
+    fn get_grid_bounds(&self, first_day: &Date<Tz>) -> (Date<Tz>, Date<Tz>) {
+        let start_offset = self.get_start_offset_of_calendar(first_day);
+        let days_in_month = get_days_from_month(first_day.year(), first_day.month());
+        let end_offset = add_months(first_day, 1, OverflowPolicy::Clamp)
+            .map_or(0, |first_day_of_next_month| {
+                self.get_end_offset_of_calendar(&first_day_of_next_month)
+            });
+        let start_date =
+            add_days(first_day, -(start_offset as i64)).unwrap_or_else(|| first_day.clone());
+        let days_count = match self.row_policy {
+            RowPolicy::Dynamic => start_offset as i64 + days_in_month + end_offset as i64,
+            RowPolicy::SixRows => 6 * 7,
+        };
+        let end_date = add_days(&start_date, days_count - 1).unwrap_or_else(|| start_date.clone());
+        (start_date, end_date)
+    }
+
+    /// Draw `months_shown` consecutive months, starting from month of selected date.
+    fn show_months(&mut self, ui: &mut Ui) {
+        if self.crosshair_on_hover {
+            // Hovered day is known only after its cell is drawn, so crosshair follows it one
+            // frame later
+            let hovered_id = self.internal_id.with("crosshair_hovered");
+            let hovered = ui.memory().data.get_temp::<NaiveDate>(hovered_id);
+            ui.memory().data.remove::<NaiveDate>(hovered_id);
+            let crosshair_id = self.internal_id.with("crosshair_day");
+            match hovered {
+                Some(day) => ui.memory().data.insert_temp(crosshair_id, day),
+                None => ui.memory().data.remove::<NaiveDate>(crosshair_id),
+            }
+        }
+        let first_day_of_current_month = self.get_first_shown_day();
+        let roving_day = self.get_roving_day(ui);
+        if self.months_shown <= 1 {
+            self.show_calendar_grid(ui, 0, &first_day_of_current_month, roving_day, None);
+            return;
+        }
+        let shared_header_width = if self.shared_weekday_header && self.month_grid_columns == 1 {
+            let width = self.get_column_width(ui);
+            let (start_date, _) = self.get_grid_bounds(&first_day_of_current_month);
+            let grid_align = self.grid_align;
+            show_aligned(
+                ui,
+                grid_align,
+                self.internal_id.with("weekday_header"),
+                |ui| {
+                    egui::Grid::new(self.internal_id.with("weekday_header"))
+                        .min_col_width(width)
+                        .show(ui, |ui| self.show_grid_header(ui, &start_date))
+                        .response
+                },
+            );
+            Some(width)
+        } else {
+            None
+        };
+        let max_height =
+            ui.ctx().input().screen_rect().bottom() - ui.available_rect_before_wrap().top();
+        egui::ScrollArea::vertical()
+            .max_height(max_height)
+            .show(ui, |ui| {
+                egui::Grid::new(self.internal_id.with("month_grid")).show(ui, |ui| {
+                    let mut first_day = first_day_of_current_month;
+                    for index in 0..self.months_shown {
+                        if index > 0 && index % self.month_grid_columns == 0 {
+                            ui.end_row();
+                        }
+                        ui.vertical(|ui| {
+                            ui.label(first_day.format("%B %Y").to_string());
+                            self.show_calendar_grid(
+                                ui,
+                                index,
+                                &first_day,
+                                roving_day,
+                                shared_header_width,
+                            );
+                        });
+                        first_day = match add_months(&first_day, 1, OverflowPolicy::Clamp) {
+                            Some(date) => date,
+                            None => break,
+                        };
+                    }
+                });
+            });
+    }
+
+    /// Get width of calendar column, wide enough for any week day name and day button, so
+    /// separate grids line up.
+    fn get_column_width(&self, ui: &Ui) -> f32 {
+        let text_width = |text: String, style: egui::TextStyle| {
+            ui.fonts()
+                .layout_no_wrap(text, style.resolve(ui.style()), Color32::WHITE)
+                .size()
+                .x
+        };
+        let weekday_width = (0..7)
+            .map(|i| {
+                text_width(
+                    Weekday::from_u8(i).unwrap().to_string(),
+                    egui::TextStyle::Body,
+                )
+            })
+            .fold(0.0, f32::max);
+        let button_width = text_width("00".to_string(), egui::TextStyle::Button)
+            + 2.0 * ui.spacing().button_padding.x;
+        let mut width = weekday_width.max(button_width);
+        if self.match_trigger_width {
+            let trigger_width = ui
+                .memory()
+                .data
+                .get_temp::<f32>(self.internal_id.with("trigger_width"));
+            if let Some(trigger_width) = trigger_width {
+                // Columns of all grids side by side, with spacing between them, fill trigger
+                let columns = 7.0 * self.month_grid_columns.min(self.months_shown).max(1) as f32;
+                let spacing = (columns - 1.0) * ui.spacing().item_spacing.x;
+                width = width.max((trigger_width - spacing) / columns);
+            }
+        }
+        if self.square_cells {
+            width.max(ui.spacing().interact_size.y)
+        } else {
+            width
+        }
+    }
+
+    /// Draw calendar grid of month starting with `first_day`. Index is position of month in
+    /// popup and keeps grid id unique. `roving_day` is the only day of all grids in tab order.
+    /// If `shared_header_width` is set, week days are already drawn above all grids and columns
+    /// get that width to line up with them.
+    fn show_calendar_grid(
+        &mut self,
+        ui: &mut Ui,
+        index: usize,
+        first_day: &Date<Tz>,
+        roving_day: Option<NaiveDate>,
+        shared_header_width: Option<f32>,
+    ) {
+        let mut grid = egui::Grid::new(self.internal_id.with(("calendar", index)));
+        if let Some(width) = shared_header_width {
+            grid = grid.min_col_width(width);
+        } else if self.square_cells || self.match_trigger_width {
+            grid = grid.min_col_width(self.get_column_width(ui));
+        }
+        let cell_side = self.square_cells.then(|| self.get_column_width(ui));
+        // Size of grid is known only after it's drawn, so background is set to placeholder
+        let background_idx = self
+            .grid_background
+            .map(|_| ui.painter().add(egui::Shape::Noop));
+        let grid_align = self.grid_align;
+        let grid_response = show_aligned(
+            ui,
+            grid_align,
+            self.internal_id.with(("grid", index)),
+            |ui| {
+                grid.show(ui, |ui| {
+                    let (start_date, end_date) = self.get_grid_bounds(first_day);
+                    if shared_header_width.is_none() {
+                        self.show_grid_header(ui, &start_date);
+                        ui.end_row();
+                    }
+                    let days_count = end_date
+                        .signed_duration_since(start_date.clone())
+                        .num_days()
+                        + 1;
+                    for i in 0..days_count {
+                        if i > 0 && i % 7 == 0 {
+                            ui.end_row();
+                        }
+                        if let Some(d) = add_days(&start_date, i) {
+                            self.show_day_button(d, first_day.month(), roving_day, cell_side, ui);
+                        }
+                    }
+                })
+                .response
+            },
+        );
+        if let (Some(color), Some(background_idx)) = (self.grid_background, background_idx) {
+            let rect = grid_response.rect.expand(ui.spacing().item_spacing.x / 2.0);
+            let rounding = ui.visuals().widgets.noninteractive.rounding;
+            ui.painter().set(
+                background_idx,
+                egui::Shape::rect_filled(rect, rounding, color),
+            );
+        }
+    }
+
+    /// Get text color of weekend day.
+    fn get_weekend_color(&self, date: &Date<Tz>, visuals: &Visuals) -> Color32 {
+        match (self.weekend_colors, date.weekday()) {
+            (Some((saturday, _)), Weekday::Sat) => saturday,
+            (Some((_, sunday)), Weekday::Sun) => sunday,
+            _ => match (self.themed_weekend_color, self.weekend_visuals_color) {
+                (Some((_, dark)), _) if visuals.dark_mode => dark,
+                (Some((light, _)), _) => light,
+                (None, Some(color)) => color(visuals),
+                (None, None) => self.weekend_color,
+            },
+        }
+    }
+
+    /// Get fill color of days between today and selected date, if they are highlighted.
+    fn get_today_range_color(&self, visuals: &Visuals) -> Option<Color32> {
+        self.today_range_visuals_color
+            .map(|color| color(visuals))
+            .or(self.today_range_color)
+    }
+
+    /// Move date into range of minimum and maximum date, so navigation can't leave it.
+    fn clamp_to_bounds(&self, date: Date<Tz>) -> Date<Tz> {
+        clamp_date(date, self.min_date.as_ref(), self.max_date.as_ref())
+    }
+
+    /// Check if day can be clicked.
+    fn is_day_enabled(&self, date: &Date<Tz>) -> bool {
+        !(self.selection_eq)(self.date, date) && self.is_day_allowed(date)
+    }
+
+    /// Check if day is between minimum and maximum date and allowed by `allowed` function.
+    fn is_day_allowed(&self, date: &Date<Tz>) -> bool {
+        !matches!(&self.min_date, Some(min_date) if min_date > date)
+            && !matches!(&self.max_date, Some(max_date) if max_date < date)
+            && (self.allowed_func)(date)
+    }
+
+    /// Get first and last day of months shown in popup.
+    fn get_shown_range(&self) -> (Date<Tz>, Date<Tz>) {
+        let first_day = self.get_first_shown_day();
+        let last_day = add_months(
+            &first_day,
+            self.months_shown.max(1) as i32,
+            OverflowPolicy::Clamp,
+        )
+        .and_then(|first_day_after| add_days(&first_day_after, -1))
+        .unwrap_or_else(|| first_day.clone());
+        (first_day, last_day)
+    }
+
+    /// Get day of shown months which is the only day of calendar grids in tab order. It's the
+    /// day last moved to with arrow keys or first enabled day of first shown month.
+    fn get_roving_day(&self, ui: &Ui) -> Option<NaiveDate> {
+        let (first_day, last_day) = self.get_shown_range();
+        let stored = ui
+            .memory()
+            .data
+            .get_temp::<(NaiveDate, bool)>(self.internal_id.with("roving_day"))
+            .and_then(|(day, _)| self.date.timezone().from_local_date(&day).single())
+            .filter(|day| first_day <= *day && *day <= last_day && self.is_day_enabled(day));
+        if let Some(day) = stored {
+            return Some(day.naive_local());
+        }
+        let days_count = last_day.signed_duration_since(first_day.clone()).num_days() + 1;
+        (0..days_count)
+            .filter_map(|i| add_days(&first_day, i))
+            .find(|date| self.is_day_enabled(date))
+            .map(|date| date.naive_local())
+    }
+
+    /// Move focus of calendar grids to next enabled day in direction of pressed arrow key. Focus
+    /// can move between shown months, but not out of them.
+    fn handle_roving_focus(&mut self, ui: &mut Ui, date: &Date<Tz>, response: &Response) {
+        let roving_id = self.internal_id.with("roving_day");
+        let stored = ui.memory().data.get_temp::<(NaiveDate, bool)>(roving_id);
+        if matches!(stored, Some((day, true)) if day == date.naive_local()) {
+            response.request_focus();
+            ui.memory()
+                .data
+                .insert_temp(roving_id, (date.naive_local(), false));
+        }
+        if !response.has_focus() {
+            return;
+        }
+        let step = {
+            let input = ui.input();
+            if input.key_pressed(Key::ArrowLeft) {
+                -1
+            } else if input.key_pressed(Key::ArrowRight) {
+                1
+            } else if input.key_pressed(Key::ArrowUp) {
+                -7
+            } else if input.key_pressed(Key::ArrowDown) {
+                7
+            } else {
+                return;
+            }
+        };
+        let (first_day, last_day) = self.get_shown_range();
+        let is_shown = |day: &Date<Tz>| first_day <= *day && *day <= last_day;
+        let mut next = add_days(date, step);
+        while let Some(day) = next.as_ref() {
+            if !is_shown(day) || self.is_day_enabled(day) {
+                break;
+            }
+            next = add_days(day, step);
+        }
+        if let Some(next) = next.filter(is_shown) {
+            ui.memory()
+                .data
+                .insert_temp(roving_id, (next.naive_local(), true));
+        }
+    }
+
+    fn show_day_button(
+        &mut self,
+        date: Date<Tz>,
+        month: u32,
+        roving_day: Option<NaiveDate>,
+        cell_side: Option<f32>,
+        ui: &mut Ui,
+    ) {
+        let selected_shape = self
+            .selected_shape
+            .filter(|_| (self.selection_eq)(self.date, &date));
+        let disabled_color = self
+            .disabled_color
+            .filter(|_| selected_shape.is_none() && !self.is_day_allowed(&date));
+        let enabled =
+            selected_shape.is_some() || disabled_color.is_some() || self.is_day_enabled(&date);
+        // Disabled ui fades everything painted in it, so column and crosshair are shaded outside
+        // of it to look the same on disabled and selected days
+        self.shade_column_cell(ui, &date);
+        self.shade_crosshair_cell(ui, &date);
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.with_layout(get_day_layout(self.day_align), |ui| {
+                if date.month() != month {
+                    // Reserve height, so rows without days of current month don't collapse
+                    let height = cell_side.unwrap_or(ui.spacing().interact_size.y);
+                    ui.allocate_space(egui::vec2(0.0, height));
+                    if let Some(empty_cell_ui) = &self.empty_cell_ui {
+                        empty_cell_ui(ui);
+                    }
+                    return;
+                }
+                if self.highlight_weekend && (self.weekend_func)(&date) {
+                    ui.style_mut().visuals.override_text_color =
+                        Some(self.get_weekend_color(&date, ui.visuals()));
+                }
+                let holiday = self.holidays.get(&date.naive_local());
+                if holiday.is_some() {
+                    ui.style_mut().visuals.override_text_color = Some(self.holiday_color);
+                }
+                let mut button = IdButton::new(
+                    self.internal_id.with(("day", date.naive_local())),
+                    date.day().to_string(),
+                );
+                if let Some(color) = self.get_today_range_color(ui.visuals()) {
+                    let today = self.today();
+                    let (from, to) = if today <= *self.date {
+                        (today, self.date.clone())
+                    } else {
+                        (self.date.clone(), today)
+                    };
+                    if from <= date && date <= to {
+                        button = button.fill(color);
+                    }
+                }
+                // Only one day of grid is in tab order, arrow keys move focus between days
+                let is_roving = roving_day == Some(date.naive_local());
+                if !is_roving {
+                    button = button.sense(Sense {
+                        focusable: false,
+                        ..Sense::click()
+                    });
+                }
+                // Placeholder is added before button, so highlight set later is drawn below its
+                // text
+                let highlight_idx = selected_shape.map(|_| ui.painter().add(egui::Shape::Noop));
+                if !self.select_enabled {
+                    button = button.sense(Sense::hover());
+                }
+                if let Some(color) = disabled_color {
+                    button = button.frame(false).sense(Sense::hover());
+                    ui.style_mut().visuals.override_text_color = Some(color);
+                }
+                if let Some((shape, _)) = selected_shape {
+                    button = button.frame(false).sense(Sense::hover());
+                    if shape != SelectedShape::Ring {
+                        ui.style_mut().visuals.override_text_color =
+                            Some(ui.visuals().selection.stroke.color);
+                    }
+                }
+                let mut response = match cell_side {
+                    Some(side) => ui.add_sized(Vec2::splat(side), button),
+                    None => ui.add(button),
+                };
+                if let Some(name) = holiday {
+                    response = response.on_hover_text(name);
+                }
+                if let Some(event_count) = self.event_count {
+                    let count = event_count(&date);
+                    if count > 0 {
+                        let text = if count > 9 {
+                            "9+".to_string()
+                        } else {
+                            count.to_string()
+                        };
+                        ui.painter().text(
+                            response.rect.right_top(),
+                            Align2::RIGHT_TOP,
+                            text,
+                            egui::TextStyle::Small.resolve(ui.style()),
+                            self.event_count_color,
+                        );
+                    }
+                }
+                if let (Some((shape, rounding)), Some(highlight_idx)) =
+                    (selected_shape, highlight_idx)
+                {
+                    let rect = response.rect;
+                    let mut selection = ui.visuals().selection;
+                    if let Some(color) = self.selected_visuals_color {
+                        selection.bg_fill = color(ui.visuals());
+                        selection.stroke = Stroke::new(selection.stroke.width, selection.bg_fill);
+                    }
+                    let highlight = match shape {
+                        SelectedShape::Fill => {
+                            egui::Shape::rect_filled(rect, rounding, selection.bg_fill)
+                        }
+                        SelectedShape::Ring => {
+                            egui::Shape::rect_stroke(rect, rounding, selection.stroke)
+                        }
+                        SelectedShape::Pill => {
+                            egui::Shape::rect_filled(rect, rect.height() / 2.0, selection.bg_fill)
+                        }
+                    };
+                    ui.painter().set(highlight_idx, highlight);
+                }
+                if let Some(on_day_rendered) = &mut self.on_day_rendered {
+                    on_day_rendered(&date, response.rect);
+                }
+                if response.hovered() {
+                    // Only days which can be clicked are previewed, others can't become selected
+                    if self.show_selection_readout && response.sense.click {
+                        ui.memory()
+                            .data
+                            .insert_temp(self.internal_id.with("hovered_day"), date.naive_local());
+                    }
+                    if self.crosshair_on_hover {
+                        ui.memory().data.insert_temp(
+                            self.internal_id.with("crosshair_hovered"),
+                            date.naive_local(),
+                        );
+                    }
+                }
+                if is_roving {
+                    self.handle_roving_focus(ui, &date, &response);
+                }
+                if response.clicked() {
+                    let accepted = match &mut self.on_select {
+                        Some(on_select) => on_select(&date),
+                        None => true,
+                    };
+                    if accepted {
+                        *self.date = date;
+                    }
+                }
+            });
+        });
+    }
+
+    /// Draw header and calendar grids below it.
+    fn show_contents(&mut self, ui: &mut Ui) {
+        self.show_header(ui);
+        ui.add_space(self.header_grid_gap);
+        self.show_months(ui);
+    }
+
+    /// Draw current month and buttons for next and previous month.
+    fn show_header(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if self.locked_month.is_some() {
+                ui.label(self.get_first_shown_day().format("%B %Y").to_string());
+                return;
+            }
+            if self.compact_header {
+                self.show_title_control(ui);
+                return;
+            }
+            match self.header_style {
+                HeaderStyle::Split => {
+                    if self.month_control {
+                        self.show_month_control(ui);
+                    }
+                    if self.year_control {
+                        self.show_year_control(ui);
+                    }
+                }
+                HeaderStyle::Unified => self.show_title_control(ui),
+            }
+            if ui.button("Today").clicked() {
+                *self.date = self.get_today_in_bounds();
+                if self.close_on_today && ui.memory().is_popup_open(self.id) {
+                    ui.memory().close_popup();
+                }
+            }
+            if self.show_header_week_number {
+                ui.label(format!("Week {}", self.date.iso_week().week()));
+            }
+        });
+        if self.show_selection_readout {
+            self.show_readout(ui);
+        }
+    }
+
+    /// Draw selected date spelled out, or hovered day as preview in weak text while pointer is over
+    /// a day which can be selected.
+    fn show_readout(&mut self, ui: &mut Ui) {
+        const READOUT_FORMAT: &str = "%A, %-d %B %Y";
+        let hovered_id = self.internal_id.with("hovered_day");
+        // Hovered day is stored by day buttons, which are drawn after header
+        let hovered_day = ui.memory().data.get_temp::<NaiveDate>(hovered_id);
+        ui.memory().data.remove::<NaiveDate>(hovered_id);
+        match hovered_day {
+            Some(day) => ui.weak(format!("Preview: {}", day.format(READOUT_FORMAT))),
+            None => ui.label(format!("Selected: {}", self.date.format(READOUT_FORMAT))),
+        };
+    }
+
+    /// Draw button with text and set current date to `new_date` when that button is clicked.
+    /// Without new date button is disabled or does nothing according to `boundary_behavior`.
+    fn date_step_button(&mut self, ui: &mut Ui, text: impl ToString, new_date: Option<Date<Tz>>) {
+        let enabled = new_date.is_some() || self.boundary_behavior == BoundaryBehavior::NoOp;
+        if ui
+            .add_enabled(enabled, egui::Button::new(text.to_string()))
+            .clicked()
+        {
+            if let Some(new_date) = new_date {
+                *self.date = new_date;
+            }
+        }
+    }
+
+    /// Get date to which month arrows step current date, see `step_month`.
+    fn get_stepped_month(&self, months: i32) -> Option<Date<Tz>> {
+        Self::step_month(
+            self.date,
+            months,
+            self.overflow_policy,
+            self.min_date.as_ref(),
+            self.max_date.as_ref(),
+        )
+    }
+
+    /// Get date to which year arrows step current date, see `step_year`.
+    fn get_stepped_year(&self, years: i32) -> Option<Date<Tz>> {
+        Self::step_year(
+            self.date,
+            years,
+            self.overflow_policy,
+            self.min_date.as_ref(),
+            self.max_date.as_ref(),
+        )
+    }
+
+    /// Get date to which year drag value moves current date when `year` is dragged to, clamped
+    /// into bounds. Returns `None` if result is out of range of `Date`.
+    fn get_dragged_year(&self, year: i32) -> Option<Date<Tz>> {
+        add_years(
+            self.date,
+            year.checked_sub(self.date.year())?,
+            self.overflow_policy,
+        )
+        .map(|date| self.clamp_to_bounds(date))
+    }
+
+    /// Get number of months stepped by month arrows.
+    fn get_month_step(&self) -> i32 {
+        if self.step_by_page {
+            self.months_shown.max(1) as i32
+        } else {
+            1
+        }
+    }
+
+    /// Draw drag value widget with current year and two buttons which substract and add one year
+    /// to current date.
+    fn show_year_control(&mut self, ui: &mut Ui) {
+        let previous = self.get_stepped_year(-1);
+        self.date_step_button(ui, "<", previous);
+
+        let min_drag = self
+            .min_date
+            .as_ref()
+            .map_or(f64::NEG_INFINITY, |date| date.year() as f64);
+        let max_drag = self
+            .max_date
+            .as_ref()
+            .map_or(f64::INFINITY, |date| date.year() as f64);
+
+        let mut drag_year = self.date.year();
+        let mut drag_value = DragValue::new(&mut drag_year).clamp_range(min_drag..=max_drag);
+        if self.year_drag_acceleration {
+            // Drag value moves with both horizontal and vertical drags
+            let pointer_speed = ui.input().pointer.velocity().length();
+            drag_value = drag_value.speed(get_year_drag_speed(pointer_speed));
+        }
+        ui.add(drag_value);
+
+        if drag_year != self.date.year() {
+            if let Some(date) = self.get_dragged_year(drag_year) {
+                *self.date = date;
+            }
+        }
+        let next = self.get_stepped_year(1);
+        self.date_step_button(ui, ">", next);
+    }
+
+    /// Draw label(will be combobox in future) with current month and two buttons which substract
+    /// and add one month to current date.
+    fn show_month_control(&mut self, ui: &mut Ui) {
+        let months = self.get_month_step();
+        let previous = self.get_stepped_month(-months);
+        self.date_step_button(ui, "<", previous);
+        let month_string = chrono::Month::from_u32(self.date.month()).unwrap().name();
+        // TODO: When https://github.com/emilk/egui/pull/543 is merged try to change label to combo box.
+        ui.add(egui::Label::new(
+            RichText::new(format!(
+                "{: <width$}",
+                month_string,
+                width = self.get_month_label_width()
+            ))
+            .text_style(egui::TextStyle::Monospace),
+        ));
+        // let mut selected = self.date.month0() as usize;
+        // egui::ComboBox::from_id_source(self.internal_id.with("month_combo_box"))
+        //     .selected_text(selected)
+        //     .show_index(ui, &mut selected, 12, |i| {
+        //         chrono::Month::from_usize(i + 1).unwrap().name().to_string()
+        //     });
+        // if selected != self.date.month0() as usize {
+        //     *self.date = self.date.with_month0(selected as u32).unwrap();
+        // }
+        let next = self.get_stepped_month(months);
+        self.date_step_button(ui, ">", next);
+    }
+
+    fn get_month_label_width(&self) -> usize {
+        self.month_label_width.unwrap_or_else(|| {
+            (1..=12)
+                .map(|month| {
+                    chrono::Month::from_u32(month)
+                        .unwrap()
+                        .name()
+                        .chars()
+                        .count()
+                })
+                .max()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Draw label with current month and year and two buttons which substract and add one month to
+    /// current date.
+    fn show_title_control(&mut self, ui: &mut Ui) {
+        let months = self.get_month_step();
+        let previous = self.get_stepped_month(-months);
+        self.date_step_button(ui, "<", previous);
+        let title = self.date.format("%B %Y").to_string();
+        // Month name, space and four digits of year
+        ui.add(egui::Label::new(
+            RichText::new(format!(
+                "{: <width$}",
+                title,
+                width = self.get_month_label_width() + 5
+            ))
+            .text_style(egui::TextStyle::Monospace),
+        ));
+        let next = self.get_stepped_month(months);
+        self.date_step_button(ui, ">", next);
+    }
+
+    /// Get position of popup top left corner, so popup is placed around button according to
+    /// `popup_anchor`. Size of popup is taken from previous frame.
+    fn get_popup_pos(&self, ui: &Ui, button_rect: Rect) -> Pos2 {
+        let size = ui
+            .memory()
+            .data
+            .get_temp::<Vec2>(self.internal_id.with("popup_size"))
+            .unwrap_or_default();
+        let x = *self
+            .popup_anchor
+            .x()
+            .align_size_within_range(size.x, button_rect.x_range())
+            .start();
+        let y = match self.popup_anchor.y() {
+            Align::Min => button_rect.top() - size.y,
+            Align::Center => button_rect.center().y - size.y / 2.0,
+            Align::Max => button_rect.bottom(),
+        };
+        egui::pos2(x, y)
+    }
+
+    /// Draw header and calendar directly into given rect, without summary button and popup. Use
+    /// this for custom layouts, where calendar must be placed precisely. Returned response covers
+    /// the rect and is marked as changed when date was changed.
+    pub fn show_in_rect(mut self, ui: &mut Ui, rect: Rect) -> DatePickerResponse {
+        let old_date = self.date.clone();
+        self.clamp_year_to_bounds();
+        let mut response = ui.allocate_rect(rect, Sense::hover());
+        let mut child_ui = ui.child_ui(rect, Layout::top_down(Align::Min));
+        child_ui.set_min_width(rect.width());
+        self.show_contents(&mut child_ui);
+        if *self.date != old_date {
+            response.mark_changed();
+        }
+        DatePickerResponse {
+            response,
+            popup_hovered: false,
+            popup_rect: None,
+            popup_response: None,
+        }
+    }
+
+    /// Draw day, month and year of date as three wheel columns, alternative to calendar grid
+    /// suited for touch. Scrolling over a column, or clicking previous or next value shown around
+    /// current one, steps that part of date. Day wraps inside month and days outside of minimum
+    /// and maximum date are left blank. Month and year are stepped like arrows of popup do, so
+    /// missing day is handled according to `overflow_policy` and result is kept between minimum
+    /// and maximum date.
+    /// Returned response is marked as changed when date was changed.
+    pub fn show_wheel(mut self, ui: &mut Ui) -> Response {
+        let old_date = self.date.clone();
+        self.clamp_year_to_bounds();
+        let mut response = ui
+            .horizontal(|ui| {
+                self.show_wheel_column(ui, "day", "%d", Self::get_wheel_day);
+                self.show_wheel_column(ui, "month", "%B", Self::get_stepped_month);
+                self.show_wheel_column(ui, "year", "%Y", Self::get_stepped_year);
+            })
+            .response;
+        if *self.date != old_date {
+            response.mark_changed();
+        }
+        response
+    }
+
+    /// Get date with day stepped inside of its month, or `None` if that day is out of bounds.
+    fn get_wheel_day(&self, days: i32) -> Option<Date<Tz>> {
+        let days_in_month = get_days_from_month(self.date.year(), self.date.month());
+        let day0 = (self.date.day0() as i64 + days as i64).rem_euclid(days_in_month);
+        self.date
+            .with_day0(day0 as u32)
+            .filter(|date| self.clamp_to_bounds(date.clone()) == *date)
+    }
+
+    /// Draw one column of wheel with previous, current and next value of date part stepped by
+    /// `step`. Values for which `step` returns `None` are left blank.
+    fn show_wheel_column(
+        &mut self,
+        ui: &mut Ui,
+        name: &str,
+        format: &str,
+        step: fn(&Self, i32) -> Option<Date<Tz>>,
+    ) {
+        let (previous, next) = (step(self, -1), step(self, 1));
+        let text = |date: &Option<Date<Tz>>| {
+            date.as_ref()
+                .map_or_else(String::new, |date| date.format(format).to_string())
+        };
+        let mut new_date = None;
+        let response = ui
+            .vertical(|ui| {
+                let weak_button = |text| egui::Button::new(RichText::new(text).weak()).frame(false);
+                if ui.add(weak_button(text(&previous))).clicked() {
+                    new_date = previous.clone();
+                }
+                ui.strong(self.date.format(format).to_string());
+                if ui.add(weak_button(text(&next))).clicked() {
+                    new_date = next.clone();
+                }
+            })
+            .response;
+
+        // Scroll is accumulated, so smooth scrolling steps once per button height
+        let scroll_id = self.internal_id.with(("wheel_scroll", name));
+        let mut scroll = ui
+            .memory()
+            .data
+            .get_temp::<f32>(scroll_id)
+            .unwrap_or_default();
+        if ui.rect_contains_pointer(response.rect) {
+            scroll += ui.input().scroll_delta.y;
+        } else {
+            scroll = 0.0;
+        }
+        let threshold = ui.spacing().interact_size.y;
+        if scroll >= threshold {
+            new_date = previous;
+            scroll = 0.0;
+        } else if scroll <= -threshold {
+            new_date = next;
+            scroll = 0.0;
+        }
+        ui.memory().data.insert_temp(scroll_id, scroll);
+
+        if let Some(new_date) = new_date {
+            *self.date = new_date;
+        }
+    }
+
+    /// Get format of summary, set either by `date_format` or `format_preset`.
+    fn get_format_string(&self) -> &str {
+        self.format_string
+            .as_deref()
+            .unwrap_or_else(|| self.format_preset.pattern())
+    }
+
+    /// Date could be set outside of bounds between frames, bring it back so year control
+    /// doesn't show out of range year. Called by every way of drawing date picker, so it
+    /// applies regardless of header style and of `year_control`.
+    fn clamp_year_to_bounds(&mut self) {
+        if matches!(&self.min_date, Some(min_date) if min_date.year() > self.date.year()) {
+            *self.date = self.min_date.clone().unwrap();
+        } else if matches!(&self.max_date, Some(max_date) if max_date.year() < self.date.year()) {
+            *self.date = self.max_date.clone().unwrap();
+        }
+    }
+
+    /// Swap inverted bounds, so grid and clamp range of year control stay valid. Called whenever
+    /// a bound is set, so drawing and `layout` always see valid bounds.
+    fn fix_inverted_bounds(&mut self) {
+        if let (Some(min_date), Some(max_date)) = (&self.min_date, &self.max_date) {
+            if min_date > max_date {
+                std::mem::swap(&mut self.min_date, &mut self.max_date);
+            }
+        }
+    }
+
+    /// Draw date picker and return response with additional information about popup. Use this
+    /// instead of `ui.add` when you need to know more than summary button response. Response is
+    /// marked as changed when date was changed.
+    pub fn show(mut self, ui: &mut Ui) -> DatePickerResponse {
+        // Clamp before summary is drawn, so it doesn't show out of range date for one frame
+        let old_date = self.date.clone();
+        self.clamp_year_to_bounds();
+        let clamped = *self.date != old_date;
+        let formated_date = self.date.format(self.get_format_string());
+        let button_response = match &self.today_summary_label {
+            Some(label) if *self.date == self.today() => self
+                .show_summary_button(ui, label.clone())
+                .on_hover_text(formated_date.to_string()),
+            _ => self.show_summary_button(ui, formated_date.to_string()),
+        };
+        let mut response = self.popup_for(ui, &button_response);
+        if clamped {
+            response.response.mark_changed();
+        }
+        response
+    }
+
+    /// Draw button which toggles popup, with `focus_id` if set.
+    fn show_summary_button(&self, ui: &mut Ui, text: String) -> Response {
+        match self.focus_id {
+            Some(id) => ui.add(IdButton::new(id, text)),
+            None => ui.button(text),
+        }
+    }
+
+    /// Draw only popup of date picker, which is toggled by clicking `trigger` instead of
+    /// summary button. Popup state is stored under id passed to `DatePicker::new`, so trigger is
+    /// tied to popup by using the same id every frame.
+    /// ```no_run
+    /// # use eframe::egui::Ui;
+    /// # use egui_datepicker::{Date, DatePicker, Utc};
+    /// # fn f(ui: &mut Ui, date: &mut Date<Utc>) {
+    /// let trigger = ui.button("📅");
+    /// DatePicker::new("trigger_datepicker", date).popup_for(ui, &trigger);
+    /// # }
+    /// ```
+    pub fn popup_for(mut self, ui: &mut Ui, trigger: &Response) -> DatePickerResponse {
+        let old_date = self.date.clone();
+        self.clamp_year_to_bounds();
+        let mut button_response = trigger.clone();
+        if button_response.clicked() {
+            if self.button_toggles {
+                ui.memory().toggle_popup(self.id);
+            } else {
+                ui.memory().open_popup(self.id);
+            }
+        }
+        if self.persist {
+            self.restore_popup_state(ui);
+        }
+
+        let mut popup_hovered = false;
+        let mut popup_rect = None;
+        let mut popup_response = None;
+        if ui.memory().is_popup_open(self.id) {
+            if self.match_trigger_width {
+                let margin = self.popup_margin.unwrap_or(ui.spacing().window_margin);
+                let width = button_response.rect.width() - margin.left - margin.right;
+                ui.memory()
+                    .data
+                    .insert_temp(self.internal_id.with("trigger_width"), width);
+            }
+            let popup_pos = self.get_popup_pos(ui, button_response.rect);
+            // Position depends on size of popup, which is known only after it's drawn, so
+            // movable popup is placed every frame until then and stays where it's dragged after
+            let placed_id = self.internal_id.with("popup_placed");
+            let placed = self.movable && ui.memory().data.get_temp::<bool>(placed_id) == Some(true);
+            let size_known = ui
+                .memory()
+                .data
+                .get_temp::<Vec2>(self.internal_id.with("popup_size"))
+                .is_some();
+            if self.movable && size_known {
+                ui.memory().data.insert_temp(placed_id, true);
+            }
+            let mut window_open = true;
+            let area_response = match self.popup_backend {
+                PopupBackend::Area => {
+                    let mut area = Area::new(self.id).order(Order::Foreground);
+                    if !placed {
+                        area = area.movable(self.movable).current_pos(popup_pos);
+                    }
+                    area.show(ui.ctx(), |ui| {
+                        let mut frame = Frame::popup(ui.style());
+                        if let Some(margin) = self.popup_margin {
+                            frame = frame.margin(margin);
+                        }
+                        frame.show(ui, |ui| self.show_contents(ui));
+                    })
+                    .response
+                }
+                PopupBackend::Window => {
+                    let title = self.date.format(self.get_format_string()).to_string();
+                    let mut window = egui::Window::new(title)
+                        .id(self.internal_id.with("window"))
+                        .open(&mut window_open);
+                    if let Some(margin) = self.popup_margin {
+                        window = window.frame(Frame::window(ui.style()).margin(margin));
+                    }
+                    if !self.movable {
+                        window = window.fixed_pos(popup_pos);
+                    } else if !placed {
+                        window = window.current_pos(popup_pos);
+                    }
+                    let window_response = window.show(ui.ctx(), |ui| self.show_contents(ui));
+                    // Window is always shown while it's open
+                    window_response.unwrap().response
+                }
+            };
+            ui.memory().data.insert_temp(
+                self.internal_id.with("popup_size"),
+                area_response.rect.size(),
+            );
+            popup_hovered = area_response.hovered();
+            popup_rect = Some(area_response.rect);
+
+            if !button_response.clicked()
+                && (!window_open
+                    || ui.input().key_pressed(Key::Escape)
+                    || self.close_on_outside_click && area_response.clicked_elsewhere())
+            {
+                ui.memory().toggle_popup(self.id);
+            }
+            popup_response = Some(area_response);
+        }
+        if self.persist {
+            let is_open = ui.memory().is_popup_open(self.id);
+            ui.memory()
+                .data
+                .insert_persisted(self.internal_id.with("popup_open"), is_open);
+        }
+        if *self.date != old_date {
+            button_response.mark_changed();
+        }
+        DatePickerResponse {
+            response: button_response,
+            popup_hovered,
+            popup_rect,
+            popup_response,
+        }
+    }
+
+    /// Open popup, if it was open when app was closed. Done only once per run, so popup isn't
+    /// reopened after other popup took its place.
+    fn restore_popup_state(&self, ui: &Ui) {
+        let restored_id = self.internal_id.with("popup_restored");
+        if ui.memory().data.get_temp::<bool>(restored_id).is_some() {
+            return;
+        }
+        ui.memory().data.insert_temp(restored_id, true);
+        if ui
+            .memory()
+            .data
+            .get_persisted::<bool>(self.internal_id.with("popup_open"))
+            == Some(true)
+        {
+            ui.memory().open_popup(self.id);
+        }
+    }
+
+    /// Step date by given number of days. Returns `None` if resulting day is before `min`, after
+    /// `max` or out of range of `Date`.
+    ///
+    /// ```no_run
+    /// # use chrono::{Date, Utc};
+    /// # use egui_datepicker::DatePicker;
+    /// # fn next_day(date: &mut Date<Utc>, max_date: &Date<Utc>) {
+    /// if let Some(next) = DatePicker::step_day(date, 1, None, Some(max_date)) {
+    ///     *date = next;
+    /// }
+    /// # }
+    /// ```
+    pub fn step_day(
+        date: &Date<Tz>,
+        days: i64,
+        min: Option<&Date<Tz>>,
+        max: Option<&Date<Tz>>,
+    ) -> Option<Date<Tz>> {
+        add_days(date, days).filter(|date| clamp_date(date.clone(), min, max) == *date)
+    }
+
+    /// Step date by given number of months exactly like month arrows of date picker do. Day of
+    /// month is kept if possible, otherwise it's handled according to `overflow`. Returns `None`
+    /// if resulting month is before month of `min`, after month of `max` or out of range of
+    /// `Date`, that's when arrow is disabled. Otherwise resulting day is clamped between `min`
+    /// and `max`.
+    pub fn step_month(
+        date: &Date<Tz>,
+        months: i32,
+        overflow: OverflowPolicy,
+        min: Option<&Date<Tz>>,
+        max: Option<&Date<Tz>>,
+    ) -> Option<Date<Tz>> {
+        add_months(date, months, overflow)
+            .filter(|date| is_month_in_bounds(date, min, max))
+            .map(|date| clamp_date(date, min, max))
+    }
+
+    /// Step date by given number of years exactly like year arrows of date picker do. 29th of
+    /// February is handled according to `overflow` in non-leap years. Returns `None` if
+    /// resulting month is before month of `min`, after month of `max` or out of range of `Date`,
+    /// that's when arrow is disabled. Otherwise resulting day is clamped between `min` and `max`.
+    pub fn step_year(
+        date: &Date<Tz>,
+        years: i32,
+        overflow: OverflowPolicy,
+        min: Option<&Date<Tz>>,
+        max: Option<&Date<Tz>>,
+    ) -> Option<Date<Tz>> {
+        add_years(date, years, overflow)
+            .filter(|date| is_month_in_bounds(date, min, max))
+            .map(|date| clamp_date(date, min, max))
+    }
+}
+
+/// Check if popup of date picker with given id is currently open. Id must be the same as the one
+/// passed to `DatePicker::new`. It doesn't depend on time zone of date picker.
+/// ```no_run
+/// # use eframe::egui::Context;
+/// # fn f(ctx: &Context) {
+/// if egui_datepicker::is_open(ctx, "super_unique_id") {
+///     // adjust surrounding layout
+/// }
+/// # }
+/// ```
+pub fn is_open<T: Hash>(ctx: &egui::Context, id: T) -> bool {
+    ctx.memory().is_popup_open(Id::new(id))
+}
+
+/// Get id of button of given day in calendar grid of date picker with given id and without
+/// `id_salt`, which is `Id::new(id).with(("day", date))`. Id is the same every frame regardless
+/// of layout, so it can be used e.g. to request focus of day.
+pub fn day_id<T: Hash>(id: T, date: NaiveDate) -> Id {
+    Id::new(id).with(("day", date))
+}
+
+impl<'a, Tz> Widget for DatePicker<'a, Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// Row of checkboxes for days of week bound to set of selected days, e.g. for weekly recurrence.
+/// Days are ordered and named the same way as in header of calendar grid.
+/// ```no_run
+/// # use std::collections::HashSet;
+/// # use chrono::Weekday;
+/// # use eframe::egui::Ui;
+/// # use egui_datepicker::WeekdayPicker;
+/// # fn f(ui: &mut Ui, days: &mut HashSet<Weekday>) {
+/// if ui.add(WeekdayPicker::new(days)).changed() {
+///     // reschedule
+/// }
+/// # }
+/// ```
+pub struct WeekdayPicker<'a> {
+    days: &'a mut HashSet<Weekday>,
+    sunday_first: bool,
+}
+
+impl<'a> WeekdayPicker<'a> {
+    /// Create new weekday picker bound to set of selected days.
+    pub fn new(days: &'a mut HashSet<Weekday>) -> Self {
+        Self {
+            days,
+            sunday_first: false,
+        }
+    }
+
+    /// If flag is set to true then first day is Sunday otherwise Monday.
+    /// Default is false
+    #[must_use]
+    pub fn sunday_first(mut self, flag: bool) -> Self {
+        self.sunday_first = flag;
+        self
+    }
+}
+
+impl Widget for WeekdayPicker<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let mut changed = false;
+        let mut response = ui
+            .horizontal(|ui| {
+                for weekday in get_weekdays(self.sunday_first) {
+                    let mut checked = self.days.contains(&weekday);
+                    if ui.checkbox(&mut checked, weekday.to_string()).changed() {
+                        changed = true;
+                        if checked {
+                            self.days.insert(weekday);
+                        } else {
+                            self.days.remove(&weekday);
+                        }
+                    }
+                }
+            })
+            .response;
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+/// Button used for calendar days and summary button with `focus_id`. Same as `egui::Button`, but
+/// with id given by caller instead of id derived from position in ui.
+struct IdButton {
+    id: Id,
+    text: String,
+    fill: Option<Color32>,
+    frame: bool,
+    sense: Sense,
+}
+
+impl IdButton {
+    fn new(id: Id, text: String) -> Self {
+        Self {
+            id,
+            text,
+            fill: None,
+            frame: true,
+            sense: Sense::click(),
+        }
+    }
+
+    fn fill(mut self, fill: Color32) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    fn frame(mut self, frame: bool) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+}
+
+impl Widget for IdButton {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let button_padding = ui.spacing().button_padding;
+        let text = egui::WidgetText::from(self.text.as_str()).into_galley(
+            ui,
+            Some(false),
+            f32::INFINITY,
+            egui::TextStyle::Button,
+        );
+        let mut desired_size = text.size() + 2.0 * button_padding;
+        desired_size.y = desired_size.y.max(ui.spacing().interact_size.y);
+        let (_, rect) = ui.allocate_space(desired_size);
+        let response = ui.interact(rect, self.id, self.sense);
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, &self.text));
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact(&response);
+            if self.frame {
+                ui.painter().rect(
+                    rect.expand(visuals.expansion),
+                    visuals.rounding,
+                    self.fill.unwrap_or(visuals.bg_fill),
+                    visuals.bg_stroke,
+                );
+            }
+            let text_pos = ui
+                .layout()
+                .align_size_within_rect(text.size(), rect.shrink2(button_padding))
+                .min;
+            text.paint_with_visuals(ui.painter(), text_pos, visuals);
+        }
+        response
+    }
+}
+
+/// Draw contents placed by `align` within width already taken in `ui`, e.g. by header above.
+/// Width of contents is known only after they are drawn, so one from previous frame is used.
+fn show_aligned(
+    ui: &mut Ui,
+    align: Align,
+    width_id: Id,
+    add_contents: impl FnOnce(&mut Ui) -> Response,
+) -> Response {
+    if align == Align::Min {
+        return add_contents(ui);
+    }
+    let width: f32 = ui.memory().data.get_temp(width_id).unwrap_or_default();
+    let free_width = (ui.min_rect().width() - width).max(0.0);
+    ui.horizontal(|ui| {
+        ui.add_space(match align {
+            Align::Center => free_width / 2.0,
+            _ => free_width,
+        });
+        let response = add_contents(ui);
+        ui.memory()
+            .data
+            .insert_temp(width_id, response.rect.width());
+        response
+    })
+    .inner
+}
+
+/// Check if month of date isn't before month of `min` or after month of `max`.
+fn is_month_in_bounds<Tz: TimeZone>(
+    date: &Date<Tz>,
+    min: Option<&Date<Tz>>,
+    max: Option<&Date<Tz>>,
+) -> bool {
+    let month = (date.year(), date.month());
+    !matches!(min, Some(min) if (min.year(), min.month()) > month)
+        && !matches!(max, Some(max) if (max.year(), max.month()) < month)
+}
+
+/// Move date into range of `min` and `max`.
+fn clamp_date<Tz: TimeZone>(
+    date: Date<Tz>,
+    min: Option<&Date<Tz>>,
+    max: Option<&Date<Tz>>,
+) -> Date<Tz> {
+    match (min, max) {
+        (Some(min), _) if &date < min => min.clone(),
+        (_, Some(max)) if &date > max => max.clone(),
+        _ => date,
+    }
+}
+
+/// Get days of week in order of columns of calendar grid.
+fn get_weekdays(sunday_first: bool) -> [Weekday; 7] {
+    if sunday_first {
+        [
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ]
+    } else {
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+    }
+}
+
+/// Get first day of week of given day. Returns `None` if it's out of range of `NaiveDate`.
+fn get_week_start(day: NaiveDate, sunday_first: bool) -> Option<NaiveDate> {
+    let offset = if sunday_first {
+        day.weekday().num_days_from_sunday()
+    } else {
+        day.weekday().num_days_from_monday()
+    };
+    day.checked_sub_signed(Duration::days(offset as i64))
+}
+
+/// Get speed of year drag value from pointer speed in points per second. It's ten points per year
+/// when dragging slowly and quadratically faster with pointer speed.
+fn get_year_drag_speed(pointer_speed: f32) -> f64 {
+    0.1 * (1.0 + pointer_speed as f64 / 200.0).powi(2)
+}
+
+/// Get layout of calendar cell which places day button according to align.
+fn get_day_layout(align: Align2) -> Layout {
+    match (align.x(), align.y()) {
+        (Align::Center, Align::Center) => Layout::centered_and_justified(Direction::TopDown),
+        (x, Align::Min) => Layout::top_down(x),
+        (x, Align::Max) => Layout::bottom_up(x),
+        (Align::Min, Align::Center) => Layout::left_to_right().with_cross_align(Align::Center),
+        (Align::Max, Align::Center) => Layout::right_to_left().with_cross_align(Align::Center),
+    }
+}
+
+// https://stackoverflow.com/a/58188385
+fn get_days_from_month(year: i32, month: u32) -> i64 {
+    NaiveDate::from_ymd(
+        match month {
+            12 => year + 1,
+            _ => year,
+        },
+        match month {
+            12 => 1,
+            _ => month + 1,
+        },
+        1,
+    )
+    .signed_duration_since(NaiveDate::from_ymd(year, month, 1))
+    .num_days()
+}
+
+/// Add days to date. Returns `None` if result is out of range of `Date`.
+fn add_days<Tz: TimeZone>(date: &Date<Tz>, days: i64) -> Option<Date<Tz>> {
+    date.clone().checked_add_signed(Duration::days(days))
+}
+
+/// Add months to date. Day of month is kept if possible, otherwise it's handled according to
+/// `overflow`. Returns `None` if result is out of range of `Date`.
+fn add_months<Tz: TimeZone>(
+    date: &Date<Tz>,
+    months: i32,
+    overflow: OverflowPolicy,
+) -> Option<Date<Tz>> {
+    let total_months = date
+        .year()
+        .checked_mul(12)?
+        .checked_add(date.month0() as i32)?
+        .checked_add(months)?;
+    let year = total_months.div_euclid(12);
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    // `get_days_from_month` panics if this or next month is out of range
+    NaiveDate::from_ymd_opt(year, month, 1)?;
+    NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?;
+    match overflow {
+        OverflowPolicy::Clamp => {
+            let day = date.day().min(get_days_from_month(year, month) as u32);
+            date.timezone().ymd_opt(year, month, day).single()
+        }
+        OverflowPolicy::Rollover => {
+            let first_day = date.timezone().ymd_opt(year, month, 1).single()?;
+            let rolled_over = add_days(&first_day, date.day() as i64 - 1)?;
+            if months != 0
+                && (rolled_over.year(), rolled_over.month()) == (date.year(), date.month())
+            {
+                add_months(date, months, OverflowPolicy::Clamp)
+            } else {
+                Some(rolled_over)
+            }
+        }
+    }
+}
+
+/// Add years to date. 29 February is handled in non-leap years according to `overflow`, i.e.
+/// it becomes 28 February or 1 March. Returns `None` if result is out of range of `Date`.
+fn add_years<Tz: TimeZone>(
+    date: &Date<Tz>,
+    years: i32,
+    overflow: OverflowPolicy,
+) -> Option<Date<Tz>> {
+    add_months(date, years.checked_mul(12)?, overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_policy_sets_number_of_rows() {
+        // February 2021 starts on Monday and has 28 days, so it fills exactly four weeks
+        let mut date = Utc.ymd(2021, 2, 10);
+        let dynamic = DatePicker::new("rows", &mut date).layout();
+        assert_eq!(dynamic.len(), 4);
+        let six_rows = DatePicker::new("rows", &mut date)
+            .row_policy(RowPolicy::SixRows)
+            .layout();
+        assert_eq!(six_rows.len(), 6);
+        assert!(six_rows.iter().all(|week| week.len() == 7));
+    }
+    #[test]
+    fn inverted_bounds_are_swapped() {
+        let mut date = Utc.ymd(2021, 3, 15);
+        let weeks = DatePicker::new("bounds", &mut date)
+            .min_date(Utc.ymd(2021, 3, 20))
+            .max_date(Utc.ymd(2021, 3, 10))
+            .layout();
+        let enabled: Vec<u32> = weeks
+            .iter()
+            .flatten()
+            .filter(|cell| !cell.is_disabled)
+            .map(|cell| cell.date.day())
+            .collect();
+        assert_eq!(enabled, (10..=20).collect::<Vec<u32>>());
+    }
+    #[test]
+    fn layout_uses_bounds_of_inverted_range() {
+        let mut date = Utc.ymd(2021, 3, 15);
+        let inverted = DatePicker::new("range", &mut date)
+            .date_range(Utc.ymd(2021, 3, 20)..=Utc.ymd(2021, 3, 10))
+            .layout();
+        let ordered = DatePicker::new("range", &mut date)
+            .date_range(Utc.ymd(2021, 3, 10)..=Utc.ymd(2021, 3, 20))
+            .layout();
+        let disabled = |weeks: &Vec<Vec<DayCell<Utc>>>| -> Vec<bool> {
+            weeks
+                .iter()
+                .flatten()
+                .map(|cell| cell.is_disabled)
+                .collect()
+        };
+        assert_eq!(disabled(&inverted), disabled(&ordered));
+        assert!(disabled(&inverted).contains(&false));
+    }
+    /// Run one frame of egui with given contents drawn in central panel.
+    fn run_frame(add_contents: impl FnOnce(&mut Ui)) {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, add_contents);
+        });
+    }
+
+    #[test]
+    fn out_of_range_year_is_clamped() {
+        let mut date = Utc.ymd(2030, 6, 15);
+        let max_date = Utc.ymd(2022, 3, 1);
+        let mut changed = false;
+        run_frame(|ui| {
+            changed = DatePicker::new("year", &mut date)
+                .max_date(max_date)
+                .show(ui)
+                .response
+                .changed();
+        });
+        assert!(changed);
+        assert_eq!(date, max_date);
+
+        let mut date = Utc.ymd(2010, 6, 15);
+        let min_date = Utc.ymd(2020, 3, 1);
+        let mut changed = false;
+        run_frame(|ui| {
+            changed = DatePicker::new("year", &mut date)
+                .min_date(min_date)
+                .header_style(HeaderStyle::Unified)
+                .show_in_rect(ui, Rect::from_min_size(Pos2::ZERO, Vec2::splat(300.0)))
+                .response
+                .changed();
+        });
+        assert!(changed);
+        assert_eq!(date, min_date);
+    }
+    #[test]
+    fn add_months_clamps_day_to_end_of_month() {
+        let clamp = OverflowPolicy::Clamp;
+        assert_eq!(
+            add_months(&Utc.ymd(2021, 1, 31), 1, clamp),
+            Some(Utc.ymd(2021, 2, 28))
+        );
+        assert_eq!(
+            add_months(&Utc.ymd(2024, 1, 31), 1, clamp),
+            Some(Utc.ymd(2024, 2, 29))
+        );
+        assert_eq!(
+            add_months(&Utc.ymd(2021, 3, 31), -1, clamp),
+            Some(Utc.ymd(2021, 2, 28))
+        );
+    }
+
+    #[test]
+    fn add_months_crosses_years() {
+        let clamp = OverflowPolicy::Clamp;
+        assert_eq!(
+            add_months(&Utc.ymd(2021, 12, 15), 1, clamp),
+            Some(Utc.ymd(2022, 1, 15))
+        );
+        assert_eq!(
+            add_months(&Utc.ymd(2022, 1, 15), -1, clamp),
+            Some(Utc.ymd(2021, 12, 15))
+        );
+        assert_eq!(
+            add_months(&Utc.ymd(2022, 3, 10), -27, clamp),
+            Some(Utc.ymd(2019, 12, 10))
+        );
+    }
+
+    #[test]
+    fn add_years_handles_leap_day() {
+        let clamp = OverflowPolicy::Clamp;
+        assert_eq!(
+            add_years(&Utc.ymd(2024, 2, 29), 1, clamp),
+            Some(Utc.ymd(2025, 2, 28))
+        );
+        assert_eq!(
+            add_years(&Utc.ymd(2024, 2, 29), -1, clamp),
+            Some(Utc.ymd(2023, 2, 28))
+        );
+        assert_eq!(
+            add_years(&Utc.ymd(2024, 2, 29), 4, clamp),
+            Some(Utc.ymd(2028, 2, 29))
+        );
+    }
+
+    #[test]
+    fn helpers_keep_fixed_offset() {
+        let offset = FixedOffset::east(5 * 3600 + 30 * 60);
+        let date = offset.ymd(2021, 1, 31);
+        let clamp = OverflowPolicy::Clamp;
+        assert_eq!(add_days(&date, 1), Some(offset.ymd(2021, 2, 1)));
+        assert_eq!(add_months(&date, 1, clamp), Some(offset.ymd(2021, 2, 28)));
+        assert_eq!(add_years(&date, -1, clamp), Some(offset.ymd(2020, 1, 31)));
+        assert_eq!(add_days(&date, 1).unwrap().offset(), &offset);
+    }
+
+    #[test]
+    fn helpers_return_none_out_of_range() {
+        let clamp = OverflowPolicy::Clamp;
+        let last = Utc.from_utc_date(&chrono::naive::MAX_DATE);
+        let first = Utc.from_utc_date(&chrono::naive::MIN_DATE);
+        assert_eq!(add_days(&last, 1), None);
+        assert_eq!(add_days(&first, -1), None);
+        assert_eq!(add_months(&last, 1, clamp), None);
+        assert_eq!(add_months(&first, -1, clamp), None);
+        assert_eq!(add_years(&last, 1, clamp), None);
+        assert_eq!(add_years(&Utc.ymd(2021, 1, 1), i32::MAX, clamp), None);
+    }
+    #[test]
+    fn navigation_stays_in_two_month_window() {
+        let min = Utc.ymd(2021, 3, 10);
+        let max = Utc.ymd(2021, 4, 20);
+        let bounds = (Some(&min), Some(&max));
+        let clamp = OverflowPolicy::Clamp;
+        let march = Utc.ymd(2021, 3, 15);
+        let april = Utc.ymd(2021, 4, 15);
+
+        // Month and page arrows
+        let step_month = |date: &Date<Utc>, months: i32| {
+            DatePicker::step_month(date, months, clamp, bounds.0, bounds.1)
+        };
+        assert_eq!(step_month(&march, -1), None);
+        assert_eq!(step_month(&march, 1), Some(april));
+        assert_eq!(step_month(&april, 1), None);
+        assert_eq!(step_month(&march, 2), None);
+        assert_eq!(
+            step_month(&Utc.ymd(2021, 4, 5), -1),
+            Some(Utc.ymd(2021, 3, 10))
+        );
+        assert_eq!(step_month(&Utc.ymd(2021, 3, 25), 1), Some(max));
+
+        // Year arrows
+        for date in [&march, &april] {
+            assert_eq!(
+                DatePicker::step_year(date, -1, clamp, bounds.0, bounds.1),
+                None
+            );
+            assert_eq!(
+                DatePicker::step_year(date, 1, clamp, bounds.0, bounds.1),
+                None
+            );
+        }
+
+        // Year drag value, Today button and wheel
+        for mut date in [march, april] {
+            let picker = DatePicker::new("window", &mut date).date_range(min..=max);
+            assert_eq!(picker.get_dragged_year(2020), Some(min));
+            assert_eq!(picker.get_dragged_year(2030), Some(max));
+            // Today is long past the window
+            assert_eq!(picker.get_today_in_bounds(), max);
+            assert_eq!(picker.get_stepped_year(-1), None);
+            assert_eq!(picker.get_stepped_year(1), None);
+        }
+        let mut date = march;
+        let picker = DatePicker::new("window", &mut date).date_range(min..=max);
+        assert_eq!(picker.get_stepped_month(-1), None);
+        assert_eq!(picker.get_stepped_month(1), Some(april));
+        assert_eq!(picker.get_wheel_day(-1), Some(Utc.ymd(2021, 3, 14)));
+        assert_eq!(picker.get_wheel_day(1), Some(Utc.ymd(2021, 3, 16)));
+        let mut date = min;
+        let picker = DatePicker::new("window", &mut date).date_range(min..=max);
+        // Wheel day wraps inside of month, but it doesn't step out of bounds
+        assert_eq!(picker.get_wheel_day(-1), None);
+        assert_eq!(picker.get_wheel_day(21), Some(Utc.ymd(2021, 3, 31)));
+        assert_eq!(picker.get_wheel_day(22), None);
+        let mut date = april;
+        let picker = DatePicker::new("window", &mut date).date_range(min..=max);
+        assert_eq!(picker.get_stepped_month(-1), Some(march));
+        assert_eq!(picker.get_stepped_month(1), None);
+        let mut date = max;
+        let picker = DatePicker::new("window", &mut date).date_range(min..=max);
+        assert_eq!(picker.get_wheel_day(1), None);
+        assert_eq!(picker.get_wheel_day(-19), Some(Utc.ymd(2021, 4, 1)));
+        assert_eq!(picker.get_wheel_day(-20), None);
+
+        // Days, by click or arrow keys, can be chosen only inside of window
+        for mut date in [march, april] {
+            let weeks = DatePicker::new("window", &mut date)
+                .date_range(min..=max)
+                .layout();
+            for cell in weeks.iter().flatten() {
+                assert_eq!(!cell.is_disabled, min <= cell.date && cell.date <= max);
+            }
+        }
+    }
+    #[test]
+    fn iso_week_format_matches_chrono() {
+        for (date, expected) in [
+            (Utc.ymd(2021, 1, 1), "2020-W53-5"),
+            (Utc.ymd(2024, 12, 30), "2025-W01-1"),
+            (Utc.ymd(2024, 3, 11), "2024-W11-1"),
+        ] {
+            let week = date.iso_week();
+            let from_chrono = format!(
+                "{}-W{:02}-{}",
+                week.year(),
+                week.week(),
+                date.weekday().number_from_monday()
+            );
+            assert_eq!(from_chrono, expected);
+            let mut selected = date;
+            let picker = DatePicker::new("summary", &mut selected).iso_week_format();
+            assert_eq!(
+                date.format(picker.get_format_string()).to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn ordinal_format_matches_chrono() {
+        for (date, expected) in [
+            (Utc.ymd(2024, 3, 15), "2024-075"),
+            (Utc.ymd(2023, 12, 31), "2023-365"),
+        ] {
+            let from_chrono = format!("{}-{:03}", date.year(), date.ordinal());
+            assert_eq!(from_chrono, expected);
+            let mut selected = date;
+            let picker = DatePicker::new("summary", &mut selected).ordinal_format();
+            assert_eq!(
+                date.format(picker.get_format_string()).to_string(),
+                expected
+            );
+        }
+    }
+    #[test]
+    fn overflow_policy_clamps_or_rolls_over() {
+        let date = Utc.ymd(2023, 1, 31);
+        let step = |overflow| DatePicker::step_month(&date, 1, overflow, None, None);
+        assert_eq!(step(OverflowPolicy::Clamp), Some(Utc.ymd(2023, 2, 28)));
+        assert_eq!(step(OverflowPolicy::Rollover), Some(Utc.ymd(2023, 3, 3)));
+        assert_eq!(
+            add_months(&Utc.ymd(2024, 1, 31), 1, OverflowPolicy::Rollover),
+            Some(Utc.ymd(2024, 3, 2))
+        );
+        assert_eq!(
+            add_years(&Utc.ymd(2024, 2, 29), 1, OverflowPolicy::Rollover),
+            Some(Utc.ymd(2025, 3, 1))
+        );
+        assert_eq!(
+            add_months(&Utc.ymd(2023, 1, 15), 1, OverflowPolicy::Rollover),
+            Some(Utc.ymd(2023, 2, 15))
+        );
+    }
+
+    #[test]
+    fn rollover_always_changes_month() {
+        // March 31st minus one month would roll over back to March 3rd
+        assert_eq!(
+            add_months(&Utc.ymd(2023, 3, 31), -1, OverflowPolicy::Rollover),
+            Some(Utc.ymd(2023, 2, 28))
+        );
+        assert_eq!(
+            DatePicker::step_month(
+                &Utc.ymd(2024, 3, 30),
+                -1,
+                OverflowPolicy::Rollover,
+                None,
+                None
+            ),
+            Some(Utc.ymd(2024, 2, 29))
+        );
+    }
+    #[test]
+    fn wheel_steps_into_bounds() {
+        let mut date = Utc.ymd(2021, 4, 5);
+        let picker = DatePicker::new("wheel", &mut date).min_date(Utc.ymd(2021, 3, 10));
+        assert_eq!(picker.get_stepped_month(-1), Some(Utc.ymd(2021, 3, 10)));
+        assert_eq!(picker.get_stepped_month(-2), None);
+        assert_eq!(picker.get_stepped_year(-1), None);
+        assert_eq!(picker.get_stepped_year(1), Some(Utc.ymd(2022, 4, 5)));
+        assert_eq!(picker.get_wheel_day(-1), Some(Utc.ymd(2021, 4, 4)));
+        assert_eq!(picker.get_wheel_day(-5), Some(Utc.ymd(2021, 4, 30)));
+    }
+    #[test]
+    fn wheel_follows_overflow_policy() {
+        let mut date = Utc.ymd(2023, 1, 31);
+        let rollover =
+            DatePicker::new("wheel", &mut date).overflow_policy(OverflowPolicy::Rollover);
+        assert_eq!(rollover.get_stepped_month(1), Some(Utc.ymd(2023, 3, 3)));
+        assert_eq!(rollover.get_stepped_month(-1), Some(Utc.ymd(2022, 12, 31)));
+        let mut date = Utc.ymd(2024, 2, 29);
+        let rollover =
+            DatePicker::new("wheel", &mut date).overflow_policy(OverflowPolicy::Rollover);
+        assert_eq!(rollover.get_stepped_year(1), Some(Utc.ymd(2025, 3, 1)));
+        let mut date = Utc.ymd(2024, 2, 29);
+        let clamp = DatePicker::new("wheel", &mut date);
+        assert_eq!(clamp.get_stepped_year(1), Some(Utc.ymd(2025, 2, 28)));
+    }
+    #[test]
+    fn week_start_is_checked() {
+        let wednesday = NaiveDate::from_ymd(2021, 3, 17);
+        assert_eq!(
+            get_week_start(wednesday, false),
+            Some(NaiveDate::from_ymd(2021, 3, 15))
+        );
+        assert_eq!(
+            get_week_start(wednesday, true),
+            Some(NaiveDate::from_ymd(2021, 3, 14))
+        );
+        let first = chrono::naive::MIN_DATE;
+        let expected = (first.weekday() == Weekday::Mon).then_some(first);
+        assert_eq!(get_week_start(first, false), expected);
+        // First day can't start week for both Monday and Sunday first, so one of them is out of
+        // range
+        assert!(get_week_start(first, false).is_none() || get_week_start(first, true).is_none());
+        assert!(get_week_start(first + Duration::days(7), false).is_some());
+    }
+    #[test]
+    fn selection_follows_local_date_near_midnight() {
+        // Half past midnight on Tuesday in UTC+13 is still Monday in UTC
+        let offset = FixedOffset::east(13 * 3600);
+        let date_time = Utc
+            .ymd(2021, 3, 15)
+            .and_hms(11, 30, 0)
+            .with_timezone(&offset);
+        assert_eq!(
+            date_time.naive_utc().date(),
+            NaiveDate::from_ymd(2021, 3, 15)
+        );
+        let mut date = date_time.date();
+        let weeks = DatePicker::new("offset", &mut date).layout();
+        let selected: Vec<&DayCell<FixedOffset>> = weeks
+            .iter()
+            .flatten()
+            .filter(|cell| cell.is_selected)
+            .collect();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(
+            selected[0].date.naive_local(),
+            NaiveDate::from_ymd(2021, 3, 16)
+        );
+        assert_eq!(selected[0].date.weekday(), Weekday::Tue);
+        // Tuesday is second column when weeks start on Monday
+        let week = weeks
+            .iter()
+            .find(|week| week.iter().any(|cell| cell.is_selected));
+        assert_eq!(week.unwrap()[1].date.day(), 16);
+    }
+    #[test]
+    fn year_drag_speed_grows_quadratically() {
+        assert!((get_year_drag_speed(0.0) - 0.1).abs() < 1e-9);
+        assert!((get_year_drag_speed(200.0) - 0.4).abs() < 1e-9);
+        assert!((get_year_drag_speed(600.0) - 1.6).abs() < 1e-9);
+        assert!(get_year_drag_speed(100.0) < get_year_drag_speed(101.0));
+        // Speed is computed from length of velocity, so vertical drags accelerate too
+        let vertical = egui::vec2(0.0, -400.0).length();
+        assert!((get_year_drag_speed(vertical) - 0.9).abs() < 1e-9);
+    }
 }